@@ -0,0 +1,92 @@
+use std::{convert::Infallible, fmt::Display};
+
+use num::BigInt;
+
+/// A puzzle answer, always a whole number or text so it prints exactly as
+/// the site expects it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u64),
+    Big(BigInt),
+    String(String),
+}
+
+impl Answer {
+    /// Name of the kind of answer.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Signed(_) => "signed",
+            Self::Unsigned(_) => "unsigned",
+            Self::Big(_) => "big",
+            Self::String(_) => "string",
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Signed(n) => write!(f, "{n}"),
+            Self::Unsigned(n) => write!(f, "{n}"),
+            Self::Big(n) => write!(f, "{n}"),
+            Self::String(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($variant:ident as $to:ty: $($from:ty),*) => {
+        $(
+            impl From<$from> for Answer {
+                fn from(n: $from) -> Self {
+                    Self::$variant(n as $to)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(Signed as i64: i32, i64, isize);
+from_int!(Unsigned as u64: u32, u64, usize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        Self::Big(n)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::String(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::String(s.to_string())
+    }
+}
+
+/// For the parts a day doesn't have.
+impl From<Infallible> for Answer {
+    fn from(i: Infallible) -> Self {
+        match i {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+
+    use super::Answer;
+
+    #[test]
+    fn display() {
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+        let big = BigInt::from(u64::MAX) * 1000;
+        assert_eq!(Answer::from(big).to_string(), "18446744073709551615000");
+        assert_eq!(Answer::from("LJ").kind(), "string");
+    }
+}
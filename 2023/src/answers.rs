@@ -0,0 +1,160 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// Name of the answers file, kept next to the inputs.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        message: String,
+    },
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "can't read {}: {error}", path.display()),
+            Self::Parse { path, message } => write!(f, "bad {}: {message}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    /// Holds the accepted answer.
+    Fail(String),
+    Missing,
+}
+
+/// Accepted answers for our inputs, a `[dayN]` table per day with `part_1`
+/// and `part_2` keys:
+///
+/// ```toml
+/// [day1]
+/// part_1 = 142
+/// part_2 = "281"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers(HashMap<(u8, u8), String>);
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let table = s.parse::<toml::Table>().map_err(|e| e.to_string())?;
+        let mut answers = HashMap::new();
+        for (key, parts) in table {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|d| d.parse::<u8>().ok())
+                .ok_or_else(|| format!("unknown table {key:?}"))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("{key} is not a table"))?;
+            for (part_key, answer) in parts {
+                let part = match part_key.as_str() {
+                    "part_1" => 1,
+                    "part_2" => 2,
+                    o => return Err(format!("unknown key {o:?} in {key}")),
+                };
+                let answer = match answer {
+                    toml::Value::Integer(i) => i.to_string(),
+                    toml::Value::String(s) => s.clone(),
+                    o => {
+                        return Err(format!(
+                            "{key}.{part_key} must be a number or string, got {o}"
+                        ))
+                    }
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Self(answers))
+    }
+
+    /// Loads the answers at `path`, a missing file has no answers.
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let s = match std::fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(error) => {
+                return Err(AnswersError::Io {
+                    path: path.to_path_buf(),
+                    error,
+                })
+            }
+        };
+        Self::parse(&s).map_err(|message| AnswersError::Parse {
+            path: path.to_path_buf(),
+            message,
+        })
+    }
+
+    /// Loads `answers.toml` from the inputs directory `dir`.
+    pub fn load_from(dir: &Path) -> Result<Self, AnswersError> {
+        Self::load(&dir.join(ANSWERS_FILE))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn verify(&self, day: u8, part: u8, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Missing,
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.to_string()),
+        }
+    }
+}
+
+/// Prints the answer and checks it against `answers.toml` when it's known.
+#[cfg(test)]
+pub(crate) fn check(day: u8, part: u8, answer: impl Display) {
+    let answer = answer.to_string();
+    println!("day {day} part {part} = {answer}");
+    let answers = Answers::load_from(&crate::input::dir()).unwrap();
+    if let Verdict::Fail(expected) = answers.verify(day, part, &answer) {
+        panic!("day {day} part {part} = {answer}, expected {expected}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answers, Verdict};
+
+    #[test]
+    fn verify() {
+        let answers = Answers::parse(
+            r#"
+[day1]
+part_1 = 142
+part_2 = "281"
+
+[day25]
+part_1 = 54
+"#,
+        )
+        .unwrap();
+        assert_eq!(answers.verify(1, 1, "142"), Verdict::Pass);
+        assert_eq!(answers.verify(1, 2, "281"), Verdict::Pass);
+        assert_eq!(answers.verify(25, 1, "55"), Verdict::Fail("54".to_string()));
+        assert_eq!(answers.verify(25, 2, "0"), Verdict::Missing);
+    }
+
+    #[test]
+    fn bad_key() {
+        assert!(Answers::parse("[day1]\npart_3 = 1\n").is_err());
+        assert!(Answers::parse("[dayone]\npart_1 = 1\n").is_err());
+    }
+}
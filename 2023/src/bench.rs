@@ -0,0 +1,105 @@
+use std::time::{Duration, Instant};
+
+use crate::solution::Entry;
+
+/// Timings of repeated runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Panics if `timings` is empty.
+    pub fn new(mut timings: Vec<Duration>) -> Self {
+        timings.sort();
+        Self {
+            min: timings[0],
+            median: timings[timings.len() / 2],
+            max: timings[timings.len() - 1],
+        }
+    }
+}
+
+/// Runs `f` `warmup` times untimed, then times `iterations` runs.
+pub fn measure<T>(warmup: usize, iterations: usize, mut f: impl FnMut() -> T) -> Stats {
+    for _ in 0..warmup {
+        std::hint::black_box(f());
+    }
+    let timings = (0..iterations.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    Stats::new(timings)
+}
+
+/// A row of the benchmark table, `part` is `None` for parsing.
+#[derive(Debug, Clone, Copy)]
+pub struct Row {
+    pub day: u8,
+    pub part: Option<u8>,
+    pub stats: Stats,
+}
+
+/// Times parsing and each part of `entry` on `input`.
+pub fn day(
+    entry: &Entry,
+    input: &str,
+    warmup: usize,
+    iterations: usize,
+) -> Result<Vec<Row>, crate::ParseError> {
+    let parsed = entry.parse(input)?;
+    let mut rows = vec![Row {
+        day: entry.day,
+        part: None,
+        stats: measure(warmup, iterations, || entry.parse(input)),
+    }];
+    for part in 1..=entry.parts {
+        rows.push(Row {
+            day: entry.day,
+            part: Some(part),
+            stats: measure(warmup, iterations, || entry.solve(&parsed, part)),
+        });
+    }
+    Ok(rows)
+}
+
+pub fn print_table(rows: &[Row]) {
+    println!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}",
+        "day", "step", "min", "median", "max"
+    );
+    for Row { day, part, stats } in rows {
+        let step = match part {
+            Some(p) => format!("part{p}"),
+            None => "parse".to_string(),
+        };
+        let Stats { min, median, max } = stats;
+        println!("{day:>3}  {step:<5}  {min:>12.3?}  {median:>12.3?}  {max:>12.3?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::new(vec![ms(5), ms(1), ms(9), ms(3), ms(4)]);
+        assert_eq!(
+            stats,
+            Stats {
+                min: ms(1),
+                median: ms(4),
+                max: ms(9),
+            }
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+/// States from step `prefix_len` on repeat every `cycle_len` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix_len: usize,
+    pub cycle_len: usize,
+}
+
+impl Cycle {
+    /// Earliest step with the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix_len {
+            n
+        } else {
+            self.prefix_len + (n - self.prefix_len) % self.cycle_len
+        }
+    }
+}
+
+/// Cycle of the states `step` goes through from `start`, loops forever if
+/// there's none.
+pub fn find<S: Hash + Eq + Clone>(start: &S, step: impl FnMut(&mut S)) -> Cycle {
+    find_within(start, step, usize::MAX).unwrap()
+}
+
+/// Same as [`find`], gives up after `limit` steps.
+pub fn find_within<S: Hash + Eq + Clone>(
+    start: &S,
+    step: impl FnMut(&mut S),
+    limit: usize,
+) -> Option<Cycle> {
+    search(start, step, limit).ok().map(|(cycle, _)| cycle)
+}
+
+/// State after `n` steps, only stepping through the first cycle.
+pub fn nth_state<S: Hash + Eq + Clone>(start: &S, mut step: impl FnMut(&mut S), n: usize) -> S {
+    match search(start, &mut step, n) {
+        // The last state found is back at the start of the cycle.
+        Ok((cycle, state)) => advance(&state, step, cycle.reduce(n) - cycle.prefix_len),
+        Err(state) => state,
+    }
+}
+
+/// Only the hashes of the states seen are kept, a state matching an earlier
+/// hash is checked by stepping from `start` again. Gives the state after
+/// `limit` steps if it doesn't find a cycle before, or the state starting
+/// the cycle with it.
+fn search<S: Hash + Eq + Clone>(
+    start: &S,
+    mut step: impl FnMut(&mut S),
+    limit: usize,
+) -> Result<(Cycle, S), S> {
+    let hash = |s: &S| {
+        let mut hasher = DefaultHasher::new();
+        s.hash(&mut hasher);
+        hasher.finish()
+    };
+    let mut seen = HashMap::<u64, Vec<usize>>::new();
+    let mut state = start.clone();
+    for i in 0.. {
+        let earlier = seen.entry(hash(&state)).or_default();
+        if let Some(&j) = earlier
+            .iter()
+            .find(|&&j| advance(start, &mut step, j) == state)
+        {
+            let cycle = Cycle {
+                prefix_len: j,
+                cycle_len: i - j,
+            };
+            return Ok((cycle, state));
+        }
+        if i == limit {
+            break;
+        }
+        earlier.push(i);
+        step(&mut state);
+    }
+    Err(state)
+}
+
+fn advance<S: Clone>(start: &S, mut step: impl FnMut(&mut S), n: usize) -> S {
+    let mut state = start.clone();
+    for _ in 0..n {
+        step(&mut state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cycle;
+
+    // 0 1 2 3 4 5 3 4 5 ...
+    fn step(s: &mut u32) {
+        *s = if *s == 5 { 3 } else { *s + 1 };
+    }
+
+    #[test]
+    fn find() {
+        let cycle = super::find(&0, step);
+        assert_eq!(
+            cycle,
+            Cycle {
+                prefix_len: 3,
+                cycle_len: 3,
+            }
+        );
+        assert_eq!((cycle.reduce(2), cycle.reduce(7)), (2, 4));
+        assert_eq!(super::find_within(&0, step, 5), None);
+        assert_eq!(super::nth_state(&0, step, 5), 5);
+        assert_eq!(super::find_within(&0, step, 6), Some(cycle));
+    }
+
+    #[test]
+    fn nth_state() {
+        assert_eq!(super::nth_state(&0, step, 4), 4);
+        assert_eq!(super::nth_state(&0, step, 1_000_000_000), 4);
+        let mut steps = 0;
+        super::nth_state(
+            &0,
+            |s| {
+                steps += 1;
+                step(s)
+            },
+            1_000_000_000,
+        );
+        assert!(steps < 20);
+    }
+}
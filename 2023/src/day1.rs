@@ -1,82 +1,146 @@
-const INPUT: &str = std::include_str!("input/day1.txt");
+use crate::{parse, ParseError, Solution};
 
-#[test]
-fn part_1() {
-    let lines = INPUT.lines();
+pub struct Day1;
 
-    let ans: u32 = lines
-        .map(|l| {
-            let (first, last) = l.chars().fold((None, None), |state, c| {
-                let c = if let Some(d) = c.to_digit(10) {
-                    d
-                } else {
-                    return state;
-                };
-                match state {
-                    (None, _) => (Some(c), None),
-                    (prev, _) => (prev, Some(c)),
-                }
-            });
-            let first = first.unwrap();
-            let last = last.unwrap_or(first);
-            first * 10 + last
-        })
-        .sum();
-    println!("day 1 part 1 = {ans}");
-}
+impl Solution for Day1 {
+    type Input = Vec<String>;
+    type Part1 = u32;
+    type Part2 = usize;
+
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Trebuchet?!";
 
-#[test]
-fn part_2() {
-    let search = [
-        "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "zero", "one", "two", "three", "four",
-        "five", "six", "seven", "eight", "nine",
-    ];
-    let lines = INPUT.lines();
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(Self::DAY, input)
+            .map(|l| {
+                l.chars(|c| c.is_ascii_alphanumeric().then_some(c))
+                    .collect::<Result<String, _>>()
+            })
+            .collect()
+    }
 
-    let ans: usize = lines
-        .map(|l| {
-            let (first, last) =
-                search
-                    .iter()
-                    .enumerate()
-                    .fold((None, None), |(first, last), (val, pat)| {
-                        let val = val % 10;
-                        let l_find = l.find(&*pat);
-                        let first = match l_find {
-                            Some(idx) => Some(
-                                first
-                                    .map(|(f_val, f_idx)| {
-                                        if idx < f_idx {
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        input
+            .iter()
+            .map(|l| {
+                let (first, last) = l.chars().fold((None, None), |state, c| {
+                    let c = if let Some(d) = c.to_digit(10) {
+                        d
+                    } else {
+                        return state;
+                    };
+                    match state {
+                        (None, _) => (Some(c), None),
+                        (prev, _) => (prev, Some(c)),
+                    }
+                });
+                let first = first.unwrap();
+                let last = last.unwrap_or(first);
+                first * 10 + last
+            })
+            .sum()
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let search = [
+            "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "zero", "one", "two", "three",
+            "four", "five", "six", "seven", "eight", "nine",
+        ];
+        input
+            .iter()
+            .map(|l| {
+                let (first, last) =
+                    search
+                        .iter()
+                        .enumerate()
+                        .fold((None, None), |(first, last), (val, pat)| {
+                            let val = val % 10;
+                            let l_find = l.find(pat);
+                            let first = match l_find {
+                                Some(idx) => Some(
+                                    first
+                                        .map(|(f_val, f_idx)| {
+                                            if idx < f_idx {
+                                                (val, idx)
+                                            } else {
+                                                (f_val, f_idx)
+                                            }
+                                        })
+                                        .unwrap_or((val, idx)),
+                                ),
+                                None => first,
+                            };
+                            let r_find = l_find.and_then(|_| l.rfind(pat));
+                            let last = match r_find {
+                                Some(idx) => Some(
+                                    last.map(|(l_val, l_idx)| {
+                                        if idx > l_idx {
                                             (val, idx)
                                         } else {
-                                            (f_val, f_idx)
+                                            (l_val, l_idx)
                                         }
                                     })
                                     .unwrap_or((val, idx)),
-                            ),
-                            None => first,
-                        };
-                        let r_find = l_find.and_then(|_| l.rfind(&*pat));
-                        let last = match r_find {
-                            Some(idx) => Some(
-                                last.map(|(l_val, l_idx)| {
-                                    if idx > l_idx {
-                                        (val, idx)
-                                    } else {
-                                        (l_val, l_idx)
-                                    }
-                                })
-                                .unwrap_or((val, idx)),
-                            ),
-                            None => last,
-                        };
-                        (first, last)
-                    });
-            let (first, _) = first.unwrap();
-            let (last, _) = last.unwrap();
-            let value = first * 10 + last;
-            value
-        })
-        .sum();
-    println!("day 1 part 2 = {ans}");
+                                ),
+                                None => last,
+                            };
+                            (first, last)
+                        });
+                let (first, _) = first.unwrap();
+                let (last, _) = last.unwrap();
+                first * 10 + last
+            })
+            .sum()
+    }
+}
+
+pub fn part_1(input: &str) -> Result<u32, ParseError> {
+    Ok(Day1::part_1(&Day1::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day1::part_2(&Day1::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE_PART_1: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+    const EXAMPLE_PART_2: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(1, |input| {
+            crate::answers::check(1, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(1, |input| {
+            crate::answers::check(1, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE_PART_1).unwrap(), 142);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE_PART_2).unwrap(), 281);
+    }
 }
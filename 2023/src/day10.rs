@@ -1,92 +1,76 @@
 use std::collections::HashSet;
 
-const INPUT: &str = std::include_str!("input/day10.txt");
-
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
-enum Direction {
-    N,
-    S,
-    W,
-    E,
-}
-
-impl Direction {
-    fn apply(&self, (i, j): (usize, usize)) -> Option<((usize, usize), Direction)> {
-        match self {
-            Direction::N => j.checked_sub(1).map(|j| ((i, j), Direction::S)),
-            Direction::S => Some(((i, j + 1), Direction::N)),
-            Direction::W => i.checked_sub(1).map(|i| ((i, j), Direction::E)),
-            Direction::E => Some(((i + 1, j), Direction::W)),
-        }
-    }
-}
+use crate::{
+    image::{self, Image},
+    parse,
+    terminal::Colour,
+    Direction, Map, ParseError, Solution, Vector,
+};
 
-#[derive(Debug)]
-enum Node {
+#[derive(Debug, Clone)]
+pub enum Node {
     Ground,
     Pipe(Direction, Direction),
     Animal,
 }
 
-impl From<char> for Node {
-    fn from(value: char) -> Self {
-        match value {
+impl Node {
+    fn glyph(&self) -> char {
+        match self {
+            Self::Ground => '.',
+            Self::Animal => 'S',
+            Self::Pipe(Direction::Up, Direction::Down) => '│',
+            Self::Pipe(Direction::Left, Direction::Right) => '─',
+            Self::Pipe(Direction::Up, Direction::Right) => '└',
+            Self::Pipe(Direction::Up, Direction::Left) => '┘',
+            Self::Pipe(Direction::Down, Direction::Left) => '┐',
+            Self::Pipe(Direction::Down, Direction::Right) => '┌',
+            Self::Pipe(..) => '?',
+        }
+    }
+
+    fn new(value: char) -> Option<Self> {
+        Some(match value {
             '.' => Self::Ground,
             'S' => Self::Animal,
-            '|' => Self::Pipe(Direction::N, Direction::S),
-            '-' => Self::Pipe(Direction::W, Direction::E),
-            'L' => Self::Pipe(Direction::N, Direction::E),
-            'J' => Self::Pipe(Direction::N, Direction::W),
-            '7' => Self::Pipe(Direction::S, Direction::W),
-            'F' => Self::Pipe(Direction::S, Direction::E),
-            o => panic!("unknown node {o}"),
-        }
+            '|' => Self::Pipe(Direction::Up, Direction::Down),
+            '-' => Self::Pipe(Direction::Left, Direction::Right),
+            'L' => Self::Pipe(Direction::Up, Direction::Right),
+            'J' => Self::Pipe(Direction::Up, Direction::Left),
+            '7' => Self::Pipe(Direction::Down, Direction::Left),
+            'F' => Self::Pipe(Direction::Down, Direction::Right),
+            _ => return None,
+        })
     }
 }
 
-struct Map {
-    nodes: Vec<Node>,
-    cols: usize,
-    animal: (usize, usize),
+#[derive(Clone)]
+pub struct Pipes {
+    map: Map<Node>,
+    animal: Vector,
 }
 
-impl Map {
-    fn new(lines: &str) -> Self {
-        let (nodes, cols, animal) = lines.lines().enumerate().fold(
-            (Vec::new(), None, None),
-            |(mut nodes, cols, mut animal), (j, l)| {
-                nodes.extend(
-                    l.chars()
-                        .enumerate()
-                        .map(|(i, c)| (i, Node::from(c)))
-                        .inspect(|(i, node)| match node {
-                            Node::Animal => animal = Some((*i, j)),
-                            _ => {}
-                        })
-                        .map(|(_, c)| c),
-                );
-                let cols = cols.or(Some(nodes.len()));
-                (nodes, cols, animal)
-            },
-        );
-        Self {
-            nodes,
-            cols: cols.unwrap(),
-            animal: animal.unwrap(),
+impl Pipes {
+    fn new(day: u8, input: &str) -> Result<Self, ParseError> {
+        let map = Map::parse(day, input, Node::new)?;
+        let second = parse::lines(day, input)
+            .flat_map(|l| l.text.match_indices('S').map(move |(_, s)| (l, s)))
+            .nth(1);
+        if let Some((l, s)) = second {
+            return Err(l.error(s, "second animal in map"));
         }
-    }
-
-    fn at(&self, (i, j): (usize, usize)) -> Option<&Node> {
-        self.nodes.get(j * self.cols + i)
-    }
-
-    fn at_mut(&mut self, (i, j): (usize, usize)) -> Option<&mut Node> {
-        self.nodes.get_mut(j * self.cols + i)
+        let animal = map
+            .nodes()
+            .iter()
+            .position(|n| matches!(n, Node::Animal))
+            .ok_or_else(|| parse::lines(day, input).end_error("no animal in map"))?;
+        let animal = map.converter().to_vector(animal);
+        Ok(Self { map, animal })
     }
 
     fn iter(&self, dir: Direction) -> NodeIter<'_> {
         NodeIter {
-            map: self,
+            pipes: self,
             pos: self.animal,
             dir,
         }
@@ -94,8 +78,8 @@ impl Map {
 }
 
 struct NodeIter<'a> {
-    map: &'a Map,
-    pos: (usize, usize),
+    pipes: &'a Pipes,
+    pos: Vector,
     dir: Direction,
 }
 
@@ -103,21 +87,17 @@ struct NodeIter<'a> {
 enum BadPipe {
     HitGround,
     DirMismatch,
-    CantMove,
     EndOfMap,
 }
 
 impl<'a> Iterator for NodeIter<'a> {
-    type Item = Result<(usize, usize), BadPipe>;
+    type Item = Result<Vector, BadPipe>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let Self { map, pos, dir } = self;
-        let (nxt_pos, match_dir) = if let Some(p) = dir.apply(*pos) {
-            p
-        } else {
-            return Some(Err(BadPipe::CantMove));
-        };
-        let node = if let Some(n) = map.at(nxt_pos) {
+        let Self { pipes, pos, dir } = self;
+        let nxt_pos = *pos + dir.to_vector();
+        let match_dir = dir.opposite();
+        let node = if let Some(n) = pipes.map.at(nxt_pos) {
             n
         } else {
             return Some(Err(BadPipe::EndOfMap));
@@ -138,7 +118,7 @@ impl<'a> Iterator for NodeIter<'a> {
         } else if *b == match_dir {
             *a
         } else {
-            println!("{dir:?} {match_dir:?}, {node:?}");
+            log::debug!("{dir:?} {match_dir:?}, {node:?}");
             return Some(Err(BadPipe::DirMismatch));
         };
 
@@ -148,81 +128,56 @@ impl<'a> Iterator for NodeIter<'a> {
     }
 }
 
-#[test]
-fn part_1() {
-    let map = Map::new(INPUT);
-    let ans = [Direction::N, Direction::S, Direction::W, Direction::E]
-        .iter()
+/// Tiles of the loop and tiles it encloses.
+fn enclosed(input: &Pipes) -> (HashSet<Vector>, Vec<Vector>) {
+    let mut pipes = input.clone();
+    let ans = Direction::all()
         .find_map(|d| {
-            map.iter(*d)
-                .try_fold(0, |sum, it| {
-                    let _ = it?;
-                    Ok::<_, BadPipe>(sum + 1)
-                })
-                .map(Some)
-                .unwrap_or_else(|e| {
-                    println!("{e:?} on {d:?}");
-                    None
-                })
-        })
-        .expect("found answer");
-    let ans = (ans + 1) / 2;
-
-    println!("day 10 part 1 = {ans}");
-}
-
-#[test]
-fn part_2() {
-    let mut map = Map::new(INPUT);
-    let ans = [Direction::N, Direction::S, Direction::W, Direction::E]
-        .iter()
-        .find_map(|d| {
-            let mut map_iter = map.iter(*d);
+            let mut map_iter = pipes.iter(d);
             match map_iter.by_ref().collect::<Result<HashSet<_>, _>>() {
-                Ok(steps) => Some((steps, *d, map_iter.dir)),
+                Ok(steps) => Some((steps, d, map_iter.dir)),
                 Err(e) => {
-                    println!("{e:?} on {d:?}");
+                    log::debug!("{e:?} on {d:?}");
                     None
                 }
             }
         })
         .expect("found answer");
     let (mut steps, da, db) = ans;
-    let (_, db) = db.apply((1, 1)).unwrap();
-    *map.at_mut(map.animal).unwrap() = Node::Pipe(da, db);
+    let db = db.opposite();
+    *pipes.map.at_mut(pipes.animal).unwrap() = Node::Pipe(da, db);
     // Animal was not part of the iterator.
-    assert!(steps.insert(map.animal));
-    let (_, ans, _) = map.nodes.iter().enumerate().fold(
-        (false, 0, None),
-        |(inloop, count, flipcond), (idx, node)| {
-            let idx = ((idx % map.cols), (idx / map.cols));
-            if idx.0 == 0 {
+    assert!(steps.insert(pipes.animal));
+    let conv = pipes.map.converter();
+    let (_, inside, _) = pipes.map.nodes().iter().enumerate().fold(
+        (false, Vec::new(), None),
+        |(inloop, mut inside, flipcond), (idx, node)| {
+            let idx = conv.to_vector(idx);
+            if idx.x == 0 {
                 assert!(!inloop, "at {idx:?}");
             }
             match node {
                 Node::Ground => {
                     if inloop {
-                        (inloop, count + 1, None)
-                    } else {
-                        (inloop, count, None)
+                        inside.push(idx);
                     }
+                    (inloop, inside, None)
                 }
                 Node::Pipe(a, b) => {
                     if steps.contains(&idx) {
                         let (flip, cond) = match (a, b) {
-                            (Direction::W, Direction::E) => (false, flipcond),
-                            (Direction::N, Direction::S) => (true, None),
-                            (o, Direction::E) => (true, Some(o)),
-                            (o, Direction::W) => (o == flipcond.expect("cond"), None),
+                            (Direction::Left, Direction::Right) => (false, flipcond),
+                            (Direction::Up, Direction::Down) => (true, None),
+                            (o, Direction::Right) => (true, Some(o)),
+                            (o, Direction::Left) => (o == flipcond.expect("cond"), None),
                             o => panic!("unexpected pipe {o:?} {a:?}, {b:?} {inloop:?}"),
                         };
-                        (inloop ^ flip, count, cond)
+                        (inloop ^ flip, inside, cond)
                     } else {
                         if inloop {
-                            (inloop, count + 1, None)
-                        } else {
-                            (inloop, count, None)
+                            inside.push(idx);
                         }
+                        (inloop, inside, None)
                     }
                 }
                 o => panic!("unexpected node {o:?}"),
@@ -230,5 +185,146 @@ fn part_2() {
         },
     );
 
-    println!("day 10 part 2 = {ans}");
+    (steps, inside)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Pipes;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Pipe Maze";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Pipes::new(Self::DAY, input)
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        let pipes = input;
+        let ans = Direction::all()
+            .find_map(|d| {
+                pipes
+                    .iter(d)
+                    .try_fold(0usize, |sum, it| {
+                        let _ = it?;
+                        Ok::<_, BadPipe>(sum + 1)
+                    })
+                    .map(Some)
+                    .unwrap_or_else(|e| {
+                        log::debug!("{e:?} on {d:?}");
+                        None
+                    })
+            })
+            .expect("found answer");
+        ans.div_ceil(2)
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        enclosed(input).1.len()
+    }
+
+    /// The loop in yellow, the tiles it encloses in green.
+    fn render(input: &Self::Input) -> Option<Image> {
+        let (pipes, inside) = enclosed(input);
+        let mut tiles = Map::from_fn(input.map.cols(), input.map.lines(), |v| {
+            match input.map.at(v) {
+                _ if pipes.contains(&v) => image::YELLOW,
+                Some(Node::Ground) => image::BLACK,
+                _ => image::GREY,
+            }
+        });
+        for v in inside {
+            *tiles.at_mut(v).unwrap() = image::GREEN;
+        }
+        Some(tiles.to_bmp(|p| *p))
+    }
+
+    /// The loop in yellow, the tiles it encloses highlighted, the animal
+    /// under the cursor.
+    fn terminal(input: &Self::Input) -> Option<String> {
+        let (pipes, inside) = enclosed(input);
+        let conv = input.map.converter();
+        let tiles = Map::from_fn(input.map.cols(), input.map.lines(), |v| {
+            let c = input.map.at(v).unwrap().glyph();
+            (
+                c,
+                if pipes.contains(&v) {
+                    Colour::Yellow
+                } else {
+                    Colour::Grey
+                },
+            )
+        });
+        let inside = inside.into_iter().filter_map(|v| conv.to_idx(v));
+        let drawn = tiles
+            .terminal(|t| *t)
+            .highlight(inside)
+            .cursor(input.animal);
+        Some(drawn.to_string())
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day10::part_1(&Day10::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day10::part_2(&Day10::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE_PART_1: &str = "\
+..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...
+";
+    const EXAMPLE_PART_2: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(10, |input| {
+            crate::answers::check(10, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(10, |input| {
+            crate::answers::check(10, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE_PART_1).unwrap(), 8);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE_PART_2).unwrap(), 10);
+    }
+
+    #[test]
+    fn second_animal() {
+        let e = super::part_2("S-7\n|.|\nL-S\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 3));
+        assert_eq!(e.message, "second animal in map");
+    }
 }
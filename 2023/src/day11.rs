@@ -1,74 +1,137 @@
 use itertools::Itertools;
 
-const INPUT: &str = std::include_str!("input/day11.txt");
-
-fn input(empty_multipler: usize) -> Vec<(usize, usize)> {
-    let cols = INPUT.lines().next().unwrap().len();
-    // First collect all galaxies.
-    let mut galaxies = INPUT
-        .lines()
-        .enumerate()
-        .map(|(j, l)| {
-            l.chars()
-                .enumerate()
-                .filter_map(move |(i, c)| (c == '#').then_some((i, j)))
-        })
-        .flatten()
-        .collect::<Vec<_>>();
-
-    // Find all rows that are empty.
-    INPUT.lines().fold(0usize, |j, l| {
-        if l.contains("#") {
-            return j + 1;
-        }
-        for (_, y) in galaxies.iter_mut() {
-            if *y > j {
-                *y = *y + empty_multipler - 1;
-            }
-        }
-        j + empty_multipler
-    });
-
-    // Find all columns that are empty.
-    (0..cols).into_iter().fold(0usize, |i, col| {
-        if INPUT
-            .lines()
-            .map(|l| l.chars().skip(col).next().unwrap())
-            .any(|c| c == '#')
-        {
-            return i + 1;
+use crate::{Map, ParseError, Solution};
+
+pub struct Universe {
+    galaxies: Vec<(usize, usize)>,
+    empty_rows: Vec<usize>,
+    empty_cols: Vec<usize>,
+}
+
+impl Universe {
+    /// Returns galaxy positions with every empty row and column replaced by
+    /// `empty_multipler` of them.
+    fn expand(&self, empty_multipler: usize) -> Vec<(usize, usize)> {
+        let grow = move |empty: &[usize], v: usize| {
+            v + empty.iter().take_while(|e| **e < v).count() * (empty_multipler - 1)
         };
-        for (x, _) in galaxies.iter_mut() {
-            if *x > i {
-                *x = *x + empty_multipler - 1;
-            }
-        }
-        i + empty_multipler
-    });
-
-    galaxies
+        self.galaxies
+            .iter()
+            .map(|(x, y)| (grow(&self.empty_cols, *x), grow(&self.empty_rows, *y)))
+            .collect()
+    }
+
+    fn distances(&self, empty_multipler: usize) -> usize {
+        self.expand(empty_multipler)
+            .into_iter()
+            .tuple_combinations()
+            .map(|((xa, ya), (xb, yb))| ya.abs_diff(yb) + xa.abs_diff(xb))
+            .sum()
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Universe;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Cosmic Expansion";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let map = Map::parse(Self::DAY, input, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        let cols = map.cols();
+        // First collect all galaxies.
+        let galaxies = map
+            .nodes()
+            .iter()
+            .positions(|galaxy| *galaxy)
+            .map(|i| (i % cols, i / cols))
+            .collect::<Vec<_>>();
+
+        // Find all rows and columns that are empty.
+        let empty_rows = (0..map.lines())
+            .filter(|row| !galaxies.iter().any(|(_, y)| y == row))
+            .collect::<Vec<_>>();
+        let empty_cols = (0..cols)
+            .filter(|col| !galaxies.iter().any(|(x, _)| x == col))
+            .collect::<Vec<_>>();
+
+        Ok(Universe {
+            galaxies,
+            empty_rows,
+            empty_cols,
+        })
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        input.distances(2)
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        input.distances(1000000)
+    }
 }
 
-#[test]
-fn part_1() {
-    let galaxies = input(2);
-    let ans: usize = galaxies
-        .into_iter()
-        .tuple_combinations()
-        .map(|((xa, ya), (xb, yb))| ya.abs_diff(yb) + xa.abs_diff(xb))
-        .sum();
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day11::part_1(&Day11::parse(input)?))
+}
 
-    println!("day 11 part 1 = {ans}");
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day11::part_2(&Day11::parse(input)?))
 }
 
-#[test]
-fn part_2() {
-    let galaxies = input(1000000);
-    let ans: usize = galaxies
-        .into_iter()
-        .tuple_combinations()
-        .map(|((xa, ya), (xb, yb))| ya.abs_diff(yb) + xa.abs_diff(xb))
-        .sum();
+#[cfg(test)]
+mod tests {
+    use super::Day11;
+    use crate::Solution;
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(11, |input| {
+            crate::answers::check(11, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(11, |input| {
+            crate::answers::check(11, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 374);
+    }
 
-    println!("day 11 part 2 = {ans}");
+    #[test]
+    fn example_expansion() {
+        assert_eq!(
+            {
+                let universe = Day11::parse(EXAMPLE).unwrap();
+                (universe.distances(10), universe.distances(100))
+            },
+            (1030, 8410)
+        );
+    }
 }
@@ -1,7 +1,6 @@
 use std::collections::HashMap;
-use std::str::FromStr as _;
 
-const INPUT: &str = std::include_str!("input/day12.txt");
+use crate::{parse, ParseError, Solution};
 
 fn check_game(
     b: &[u8],
@@ -18,7 +17,7 @@ fn check_game(
     let owned_damaged = damaged.to_vec();
     let key = (owned_b, owned_damaged);
     if let Some(v) = memory.get(&key) {
-        return v.clone();
+        return *v;
     }
 
     let mut eval = |head_damaged: bool| -> Option<usize> {
@@ -27,13 +26,13 @@ fn check_game(
             if b.len() < want {
                 return None;
             }
-            if b[..want].iter().any(|b| *b == '.' as u8) {
+            if b[..want].contains(&b'.') {
                 return None;
             }
             if b.len() == want {
                 return check_game(&[], &damaged[1..], memory);
             }
-            if b[want] == '#' as u8 {
+            if b[want] == b'#' {
                 return None;
             }
             check_game(&b[want + 1..], &damaged[1..], memory)
@@ -56,53 +55,107 @@ fn check_game(
         }
         o => panic!("unexpected char {o}"),
     };
-    memory.insert(key, result.clone());
+    memory.insert(key, result);
     result
 }
 
-#[test]
-fn part_1() {
-    let ans: usize = INPUT
-        .lines()
-        .map(|l| {
-            let (map, damaged) = l.split_once(" ").unwrap();
-            let damaged = damaged
-                .split(",")
-                .map(|v| usize::from_str(v).unwrap())
-                .collect::<Vec<_>>();
-            let result = check_game(map.as_bytes(), &damaged[..], &mut HashMap::new());
-            result.unwrap_or(0)
-        })
-        .sum();
-    println!("day 12 part 1 = {ans}");
-}
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<(Vec<u8>, Vec<usize>)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Hot Springs";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(Self::DAY, input)
+            .map(|l| {
+                let (map, damaged) = l.split_once(l.text, " ")?;
+                let map = l
+                    .chars_in(map, |c| matches!(c, '.' | '#' | '?').then_some(c as u8))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let damaged = damaged
+                    .split(",")
+                    .map(|v| l.parse(v))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((map, damaged))
+            })
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        input
+            .iter()
+            .map(|(map, damaged)| {
+                let result = check_game(&map[..], &damaged[..], &mut HashMap::new());
+                result.unwrap_or(0)
+            })
+            .sum()
+    }
 
-#[test]
-fn part_2() {
-    let ans: usize = INPUT
-        .lines()
-        .map(|l| {
-            let (map, damaged) = l.split_once(" ").unwrap();
-            let damaged = damaged
-                .split(",")
-                .map(|v| usize::from_str(v).unwrap())
-                .collect::<Vec<_>>();
-
-            let damaged = std::iter::repeat(damaged.iter().copied())
-                .take(5)
-                .flatten()
-                .collect::<Vec<_>>();
-
-            let map =
-                std::iter::repeat(itertools::Either::Left(map.as_bytes().iter().copied())).take(5);
-            let map =
-                itertools::intersperse(map, itertools::Either::Right(std::iter::once('?' as u8)))
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        input
+            .iter()
+            .map(|(map, damaged)| {
+                let damaged = std::iter::repeat_n(damaged.iter().copied(), 5)
                     .flatten()
                     .collect::<Vec<_>>();
 
-            let result = check_game(&map[..], &damaged[..], &mut HashMap::new());
-            result.unwrap_or(0)
-        })
-        .sum();
-    println!("day 12 part 2 = {ans}");
+                let map = std::iter::repeat_n(itertools::Either::Left(map.iter().copied()), 5);
+                let map =
+                    itertools::intersperse(map, itertools::Either::Right(std::iter::once(b'?')))
+                        .flatten()
+                        .collect::<Vec<_>>();
+
+                let result = check_game(&map[..], &damaged[..], &mut HashMap::new());
+                result.unwrap_or(0)
+            })
+            .sum()
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day12::part_1(&Day12::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day12::part_2(&Day12::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(12, |input| {
+            crate::answers::check(12, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(12, |input| {
+            crate::answers::check(12, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 21);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 525152);
+    }
 }
@@ -1,15 +1,16 @@
 use std::cmp;
-use std::collections::HashMap;
-use std::fmt::Display;
 
-const INPUT: &str = std::include_str!("input/day14.txt");
+use crate::{
+    cycle,
+    image::{self, Image, Pixel, Recorder},
+    Direction, Map, ParseError, Solution, Vector,
+};
 
-fn load<'a>(lines: usize, iter: impl Iterator<Item = &'a str>) -> usize {
+fn load<'a>(lines: usize, iter: impl Iterator<Item = &'a [char]>) -> usize {
     let (_, a, _) = iter.fold((None, 0, lines), |(mut omem, mut sum, w), l| {
-        let mem =
-            omem.get_or_insert_with(|| std::iter::repeat(w).take(l.len()).collect::<Vec<_>>());
+        let mem = omem.get_or_insert_with(|| std::iter::repeat_n(w, l.len()).collect::<Vec<_>>());
         let w = w - 1;
-        for (j, c) in l.chars().enumerate() {
+        for (j, c) in l.iter().enumerate() {
             match c {
                 'O' => {
                     let mem = &mut mem[j];
@@ -30,233 +31,199 @@ fn load<'a>(lines: usize, iter: impl Iterator<Item = &'a str>) -> usize {
     a
 }
 
-#[test]
-fn part_1() {
-    let lines = INPUT.lines().count();
-    let ans = load(lines, INPUT.lines());
+fn shift_params(cols: usize, rows: usize, d: Direction) -> (impl Iterator<Item = Vector>, Vector) {
+    let at = |x: usize, y: usize| Vector::new(x as isize, y as isize);
+    match d {
+        Direction::Up => {
+            let iter = (0..rows).flat_map(move |y| (0..cols).map(move |x| at(x, y)));
+            (
+                itertools::Either::Left(itertools::Either::Left(iter)),
+                Vector::new(0, 1),
+            )
+        }
+        Direction::Left => {
+            let iter = (0..cols).flat_map(move |x| (0..rows).map(move |y| at(x, y)));
+            (
+                itertools::Either::Right(itertools::Either::Left(iter)),
+                Vector::new(1, 0),
+            )
+        }
+        Direction::Down => {
+            let iter = (0..rows)
+                .rev()
+                .flat_map(move |y| (0..cols).map(move |x| at(x, y)));
+            (
+                itertools::Either::Left(itertools::Either::Right(iter)),
+                Vector::new(0, -1),
+            )
+        }
+        Direction::Right => {
+            let iter = (0..cols)
+                .rev()
+                .flat_map(move |x| (0..rows).map(move |y| at(x, y)));
+            (
+                itertools::Either::Right(itertools::Either::Right(iter)),
+                Vector::new(-1, 0),
+            )
+        }
+        d => panic!("can't tilt {d:?}"),
+    }
+}
 
-    println!("day 14 part 1 = {ans}");
+fn shift(map: &mut Map<char>, d: Direction) {
+    let (iter, delta) = shift_params(map.cols(), map.lines(), d);
+    let mut mem = Vec::new();
+    mem.resize(cmp::max(map.cols(), map.lines()), None);
+    for pos in iter {
+        let c = map.at_mut(pos).unwrap();
+        let mem_idx = pos.dot(delta.yx()).unsigned_abs();
+
+        match *c {
+            '.' => {
+                let mem = &mut mem[mem_idx];
+                if mem.is_none() {
+                    *mem = Some(pos);
+                }
+            }
+            '#' => {
+                mem[mem_idx] = None;
+            }
+            'O' => {
+                let mem = &mut mem[mem_idx];
+                if let Some(npos) = mem.take() {
+                    *c = '.';
+                    *map.at_mut(npos).unwrap() = 'O';
+                    *mem = Some(npos + delta);
+                }
+            }
+            c => panic!("unknown {c:?}"),
+        }
+    }
 }
 
-#[derive(Debug, Hash, Eq, PartialEq, Copy, Clone, Default)]
-struct Pos(isize, isize);
+fn score(map: &Map<char>) -> usize {
+    shift_params(map.cols(), map.lines(), Direction::Up)
+        .0
+        .map(|pos| {
+            if map.at(pos) != Some(&'O') {
+                return 0usize;
+            }
+            map.lines() - (pos.y as usize)
+        })
+        .sum()
+}
 
-impl Pos {
-    fn add(&self, Pos(ox, oy): Pos) -> Pos {
-        let Pos(x, y) = self;
-        Pos(*x + ox, *y + oy)
-    }
+const SPIN: [Direction; 4] = [
+    Direction::Up,
+    Direction::Left,
+    Direction::Down,
+    Direction::Right,
+];
 
-    fn dot(&self, Pos(dx, dy): Pos) -> isize {
-        let Pos(x, y) = self;
-        *x * dx + *y * dy
+fn spin(map: &mut Map<char>) {
+    for d in SPIN {
+        shift(map, d);
     }
+}
 
-    fn flip(&self) -> Pos {
-        let Pos(x, y) = self;
-        Pos(*y, *x)
+fn colour(c: &char) -> Pixel {
+    match c {
+        'O' => image::WHITE,
+        '#' => image::GREY,
+        _ => image::BLACK,
     }
 }
 
-struct Map {
-    values: Vec<u8>,
-    cols: usize,
-    rows: usize,
-}
+pub struct Day14;
 
-impl Map {
-    fn new(lines: &str) -> Map {
-        let cols = lines.lines().next().unwrap().len();
-        let values: Vec<_> = lines
-            .lines()
-            .map(|l| l.as_bytes().iter().copied())
-            .flatten()
-            .collect();
-        let rows = values.len() / cols;
-        Self { values, cols, rows }
-    }
+impl Solution for Day14 {
+    type Input = Map<char>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    fn shift_params(&self, d: Direction) -> (impl Iterator<Item = Pos>, Pos) {
-        let rows = self.rows;
-        let cols = self.cols;
-        match d {
-            Direction::North => {
-                let iter = (0..rows)
-                    .into_iter()
-                    .map(move |y| {
-                        (0..cols)
-                            .into_iter()
-                            .map(move |x| Pos(x as isize, y as isize))
-                    })
-                    .flatten();
-                (
-                    itertools::Either::Left(itertools::Either::Left(iter)),
-                    Pos(0, 1),
-                )
-            }
-            Direction::West => {
-                let iter = (0..cols)
-                    .into_iter()
-                    .map(move |x| {
-                        (0..rows)
-                            .into_iter()
-                            .map(move |y| Pos(x as isize, y as isize))
-                    })
-                    .flatten();
-                (
-                    itertools::Either::Right(itertools::Either::Left(iter)),
-                    Pos(1, 0),
-                )
-            }
-            Direction::South => {
-                let iter = (0..rows)
-                    .rev()
-                    .into_iter()
-                    .map(move |y| {
-                        (0..cols)
-                            .into_iter()
-                            .map(move |x| Pos(x as isize, y as isize))
-                    })
-                    .flatten();
-                (
-                    itertools::Either::Left(itertools::Either::Right(iter)),
-                    Pos(0, -1),
-                )
-            }
-            Direction::East => {
-                let iter = (0..cols)
-                    .rev()
-                    .into_iter()
-                    .map(move |x| {
-                        (0..rows)
-                            .into_iter()
-                            .map(move |y| Pos(x as isize, y as isize))
-                    })
-                    .flatten();
-                (
-                    itertools::Either::Right(itertools::Either::Right(iter)),
-                    Pos(-1, 0),
-                )
-            }
-        }
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Parabolic Reflector Dish";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Map::parse(Self::DAY, input, |c| {
+            matches!(c, '.' | '#' | 'O').then_some(c)
+        })
     }
 
-    fn at(&mut self, Pos(x, y): Pos) -> &mut u8 {
-        let map_idx = (y as usize) * self.cols + (x as usize);
-        &mut self.values[map_idx]
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        load(input.lines(), input.rows())
     }
 
-    fn get(&self, Pos(x, y): Pos) -> char {
-        let map_idx = (y as usize) * self.cols + (x as usize);
-        self.values[map_idx] as char
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let map = cycle::nth_state(input, spin, 1_000_000_000);
+        score(&map)
     }
 
-    fn shift(&mut self, d: Direction) {
-        let (iter, delta) = self.shift_params(d);
-        let mut mem = Vec::new();
-        mem.resize(cmp::max(self.cols, self.rows), None);
-        for pos in iter {
-            let c = self.at(pos);
-            let mem_idx = pos.dot(delta.flip()).abs() as usize;
+    /// Rounded rocks in white once tilted north, cube rocks in grey.
+    fn render(input: &Self::Input) -> Option<Image> {
+        let mut map = input.clone();
+        shift(&mut map, Direction::Up);
+        Some(map.to_bmp(colour))
+    }
 
-            match *c as char {
-                '.' => {
-                    let mem = &mut mem[mem_idx];
-                    if mem.is_none() {
-                        *mem = Some(pos);
-                    }
-                }
-                '#' => {
-                    mem[mem_idx] = None;
-                }
-                'O' => {
-                    let mem = &mut mem[mem_idx];
-                    if let Some(npos) = mem.take() {
-                        *c = '.' as u8;
-                        *self.at(npos) = 'O' as u8;
-                        *mem = Some(npos.add(delta));
-                    }
-                }
-                c => panic!("unknown {c:?}"),
+    /// Every tilt of the spin cycles until the rocks repeat.
+    fn animate(input: &Self::Input, recorder: &mut Recorder) {
+        let cycle = cycle::find(input, spin);
+        let mut map = input.clone();
+        recorder.record(&map, colour);
+        for _ in 0..cycle.prefix_len + cycle.cycle_len {
+            for d in SPIN {
+                shift(&mut map, d);
+                recorder.record(&map, colour);
             }
         }
     }
-
-    fn score(&self) -> usize {
-        self.shift_params(Direction::North)
-            .0
-            .map(|pos| {
-                if self.get(pos) != 'O' {
-                    return 0usize;
-                }
-                self.rows - (pos.1 as usize)
-            })
-            .sum()
-    }
 }
 
-impl Display for Map {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for i in 0..self.rows {
-            let beg = i * self.cols;
-            let end = beg + self.cols;
-            write!(f, "{}\n", String::from_utf8_lossy(&self.values[beg..end]))?;
-        }
-        Ok(())
-    }
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day14::part_1(&Day14::parse(input)?))
 }
 
-#[derive(Debug, Copy, Clone)]
-enum Direction {
-    North,
-    West,
-    South,
-    East,
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day14::part_2(&Day14::parse(input)?))
 }
 
-#[test]
-fn part_2() {
-    let mut map = Map::new(INPUT);
-    let mut mem = HashMap::<Vec<u8>, Vec<u8>>::new();
-    let rounds = 1000000000;
-    let mut f = None;
-    for j in 0..rounds {
-        match mem.entry(map.values.clone()) {
-            std::collections::hash_map::Entry::Occupied(_) => {
-                f = Some(j);
-                break;
-            }
-            std::collections::hash_map::Entry::Vacant(v) => {
-                for d in [
-                    Direction::North,
-                    Direction::West,
-                    Direction::South,
-                    Direction::East,
-                ] {
-                    map.shift(d);
-                }
-                v.insert(map.values.clone());
-            }
-        }
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(14, |input| {
+            crate::answers::check(14, 1, super::part_1(input).unwrap());
+        });
     }
-    // Find the loop length from where I am;
-    let mut i = 0;
-    let mut v = &map.values;
-    let loop_len = loop {
-        let nv = mem.get(v).unwrap();
-        i += 1;
-        if nv == &map.values {
-            break i;
-        }
-        v = nv;
-    };
-    let rem = (rounds - f.unwrap()) % loop_len;
-    v = &map.values;
-    for _ in 0..rem {
-        v = mem.get(v).unwrap();
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(14, |input| {
+            crate::answers::check(14, 2, super::part_2(input).unwrap());
+        });
     }
-    map.values = v.clone();
 
-    let ans = map.score();
-    println!("Day 14 part 2 = {ans}");
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 136);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 64);
+    }
 }
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-const INPUT: &str = std::include_str!("input/day15.txt");
+use crate::{parse, ParseError, Solution};
 
 fn hash(cur: usize, c: char) -> usize {
     if c == '\n' {
@@ -12,60 +12,118 @@ fn hash(cur: usize, c: char) -> usize {
     cur % 256
 }
 
-#[test]
-fn part_1() {
-    let ans: usize = INPUT.split(",").map(|s| s.chars().fold(0, hash)).sum();
+pub struct Day15;
 
-    println!("day 15 part 1 = {ans}");
-}
+impl Solution for Day15 {
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-#[test]
-fn part_2() {
-    let mut boxes = Vec::new();
-    boxes.resize(256, Vec::<(String, usize)>::new());
-
-    let boxes = INPUT.trim().split(",").fold(boxes, |mut boxes, s| {
-        let cmd = s.find(&['-', '=']).unwrap();
-        let label = &s[0..cmd];
-        let what = &s[cmd..cmd + 1];
-        let hash = label.chars().fold(0, hash);
-        let bx = &mut boxes[hash];
-        match what {
-            "=" => {
-                let v = &s[cmd + 1..];
-                let v = usize::from_str(v).unwrap();
-                match bx.iter_mut().find(|(l, _)| l == label) {
-                    Some((_, old)) => {
-                        *old = v;
-                    }
-                    None => {
-                        bx.push((label.to_string(), v));
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Lens Library";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let step = regex::Regex::new("^[a-z]+(-|=[0-9]+)$").unwrap();
+        parse::lines(Self::DAY, input)
+            .filter(|l| !l.text.is_empty())
+            .flat_map(|l| l.text.split(",").map(move |s| (l, s)))
+            .map(|(l, s)| {
+                if !step.is_match(s) {
+                    return Err(l.error(s, format!("doesn't match {step}")));
+                }
+                Ok(s.to_string())
+            })
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        input.iter().map(|s| s.chars().fold(0, hash)).sum()
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let mut boxes = Vec::new();
+        boxes.resize(256, Vec::<(String, usize)>::new());
+
+        let boxes = input.iter().fold(boxes, |mut boxes, s| {
+            let cmd = s.find(['-', '=']).unwrap();
+            let label = &s[0..cmd];
+            let what = &s[cmd..cmd + 1];
+            let hash = label.chars().fold(0, hash);
+            let bx = &mut boxes[hash];
+            match what {
+                "=" => {
+                    let v = &s[cmd + 1..];
+                    let v = usize::from_str(v).unwrap();
+                    match bx.iter_mut().find(|(l, _)| l == label) {
+                        Some((_, old)) => {
+                            *old = v;
+                        }
+                        None => {
+                            bx.push((label.to_string(), v));
+                        }
                     }
                 }
+                "-" => {
+                    bx.retain(|(l, _)| l != label);
+                }
+                o => panic!("don't know {o}"),
             }
-            "-" => {
-                bx.retain(|(l, _)| l != label);
-            }
-            o => panic!("don't know {o}"),
-        }
+            boxes
+        });
+
         boxes
-    });
-
-    let ans: usize = boxes
-        .into_iter()
-        .enumerate()
-        .map(|(i, lenses)| {
-            let i = i + 1;
-            lenses
-                .into_iter()
-                .enumerate()
-                .map(|(l, (_, v))| {
-                    let l = l + 1;
-                    i * l * v
-                })
-                .sum::<usize>()
-        })
-        .sum();
-
-    println!("day 15 part 2  = {ans}");
+            .into_iter()
+            .enumerate()
+            .map(|(i, lenses)| {
+                let i = i + 1;
+                lenses
+                    .into_iter()
+                    .enumerate()
+                    .map(|(l, (_, v))| {
+                        let l = l + 1;
+                        i * l * v
+                    })
+                    .sum::<usize>()
+            })
+            .sum()
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day15::part_1(&Day15::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day15::part_2(&Day15::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(15, |input| {
+            crate::answers::check(15, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(15, |input| {
+            crate::answers::check(15, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 1320);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 145);
+    }
 }
@@ -1,16 +1,20 @@
-use std::fmt::Debug;
+use std::fmt::Display;
 
-const INPUT: &str = std::include_str!("input/day16.txt");
-
-type Vector = euclid::Vector2D<isize, ()>;
+use crate::{
+    image::{self, Image, Pixel, Recorder},
+    terminal::Colour,
+    Map, ParseError, Solution, Vector,
+};
 
+#[derive(Clone)]
 enum NodeType {
     Ground,
     Mirror(isize),
     Splitter(Vector),
 }
 
-struct Node {
+#[derive(Clone)]
+pub struct Node {
     ty: NodeType,
     energized: u8,
 }
@@ -29,7 +33,7 @@ impl Node {
     }
 }
 
-impl Debug for Node {
+impl Display for Node {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.energized() {
             return write!(f, "#");
@@ -64,146 +68,230 @@ impl From<NodeType> for Node {
     }
 }
 
-struct Map {
-    nodes: Vec<Node>,
-    cols: usize,
+fn input(day: u8, s: &str) -> Result<Map<Node>, ParseError> {
+    Map::parse(day, s, |c| {
+        let ty = match c {
+            '.' => NodeType::Ground,
+            '/' => NodeType::Mirror(-1),
+            '\\' => NodeType::Mirror(1),
+            '-' => NodeType::Splitter(Vector::new(1, 0)),
+            '|' => NodeType::Splitter(Vector::new(0, 1)),
+            _ => return None,
+        };
+        Some(Node::from(ty))
+    })
 }
 
-impl Map {
-    fn new(s: &str) -> Self {
-        s.lines()
-            .fold(None, |mut map, l| {
-                let m = map.get_or_insert_with(|| Self {
-                    nodes: Vec::new(),
-                    cols: l.len(),
-                });
-
-                m.nodes.extend(l.chars().map(|c| {
-                    match c {
-                        '.' => NodeType::Ground,
-                        '/' => NodeType::Mirror(-1),
-                        '\\' => NodeType::Mirror(1),
-                        '-' => NodeType::Splitter(Vector::new(1, 0)),
-                        '|' => NodeType::Splitter(Vector::new(0, 1)),
-                        o => panic!("unknown node {o:?}"),
-                    }
-                    .into()
-                }));
-
-                map
-            })
-            .unwrap()
-    }
-
-    fn at(&mut self, Vector { x, y, .. }: Vector) -> Option<&mut Node> {
-        let x = usize::try_from(x).ok()?;
-        if x >= self.cols {
-            return None;
+fn visit(map: &mut Map<Node>, mut pos: Vector, mut speed: Vector) -> usize {
+    let mut energized = 0;
+    loop {
+        let node = if let Some(n) = map.at_mut(pos) {
+            n
+        } else {
+            return energized;
+        };
+        let (stop, bef) = node.energize_with(speed);
+        if stop {
+            break energized;
+        }
+        if bef == 0 {
+            energized += 1;
         }
-        let y = usize::try_from(y).ok()?;
-        self.nodes.get_mut(y * self.cols + x)
-    }
 
-    fn visit(&mut self, mut pos: Vector, mut speed: Vector) -> usize {
-        let mut energized = 0;
-        loop {
-            let node = if let Some(n) = self.at(pos) {
-                n
-            } else {
-                return energized;
-            };
-            let (stop, bef) = node.energize_with(speed);
-            if stop {
-                break energized;
+        match node.ty {
+            NodeType::Ground => {}
+            NodeType::Mirror(mul) => {
+                speed = speed.yx() * mul;
             }
-            if bef == 0 {
-                energized += 1;
-            }
-
-            match node.ty {
-                NodeType::Ground => {}
-                NodeType::Mirror(mul) => {
-                    speed = speed.yx() * mul;
-                }
-                NodeType::Splitter(dir) => {
-                    if speed.dot(dir) == 0 {
-                        speed = speed.yx();
-                        energized += self.visit(pos + speed, speed);
-                        speed = speed * -1;
-                    }
+            NodeType::Splitter(dir) => {
+                if speed.dot(dir) == 0 {
+                    speed = speed.yx();
+                    energized += visit(map, pos + speed, speed);
+                    speed *= -1;
                 }
             }
-            pos += speed
         }
+        pos += speed
     }
+}
 
-    fn reset(&mut self) {
-        for n in self.nodes.iter_mut() {
-            n.energized = 0;
+/// Same as [`visit`] moving every beam one tile at a time, recording the
+/// map after each step.
+fn propagate(map: &mut Map<Node>, pos: Vector, speed: Vector, recorder: &mut Recorder) {
+    let mut beams = vec![(pos, speed)];
+    while !beams.is_empty() {
+        let mut next = Vec::new();
+        for (pos, speed) in beams {
+            let Some(node) = map.at_mut(pos) else {
+                continue;
+            };
+            if node.energize_with(speed).0 {
+                continue;
+            }
+            let speeds = match node.ty {
+                NodeType::Mirror(mul) => vec![speed.yx() * mul],
+                NodeType::Splitter(dir) if speed.dot(dir) == 0 => vec![speed.yx(), speed.yx() * -1],
+                _ => vec![speed],
+            };
+            next.extend(speeds.into_iter().map(|s| (pos + s, s)));
         }
+        recorder.record(map, colour);
+        beams = next;
     }
+}
 
-    fn lines(&self) -> usize {
-        self.nodes.len() / self.cols
+fn colour(n: &Node) -> Pixel {
+    match n.ty {
+        _ if n.energized() => image::YELLOW,
+        NodeType::Ground => image::BLACK,
+        _ => image::GREY,
     }
 }
 
-impl std::fmt::Debug for Map {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for (i, n) in self.nodes.iter().enumerate() {
-            write!(f, "{n:?}")?;
-            if (i + 1) % self.cols == 0 {
-                write!(f, "\n")?;
-            }
-        }
-        Ok(())
+fn reset(map: &mut Map<Node>) {
+    for n in map.nodes_mut() {
+        n.energized = 0;
     }
 }
 
-#[test]
-fn part_1() {
-    let mut map = Map::new(INPUT);
-    let ans = map.visit(Vector::zero(), Vector::new(1, 0));
-    println!("day 16 part 1 = {ans}");
-}
+pub struct Day16;
 
-#[test]
-fn part_2() {
-    let mut map = Map::new(INPUT);
+impl Solution for Day16 {
+    type Input = Map<Node>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let lines = map.lines() as isize;
-    let cols = map.cols as isize;
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "The Floor Will Be Lava";
 
-    let lines_iter = (0..lines)
-        .into_iter()
-        .map(|l| {
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        self::input(Self::DAY, input)
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        let mut map = input.clone();
+        visit(&mut map, Vector::zero(), Vector::new(1, 0))
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let mut map = input.clone();
+
+        let lines = map.lines() as isize;
+        let cols = map.cols() as isize;
+
+        let lines_iter = (0..lines).flat_map(|l| {
             [
                 (Vector::new(0, l), Vector::new(1, 0)),
-                (Vector::new(lines - 1, l), Vector::new(-1, 0)),
+                (Vector::new(cols - 1, l), Vector::new(-1, 0)),
             ]
-            .into_iter()
-        })
-        .flatten();
-    let cols_iter = (0..cols)
-        .into_iter()
-        .map(|c| {
+        });
+        let cols_iter = (0..cols).flat_map(|c| {
             [
                 (Vector::new(c, 0), Vector::new(0, 1)),
-                (Vector::new(c, cols - 1), Vector::new(0, -1)),
+                (Vector::new(c, lines - 1), Vector::new(0, -1)),
             ]
-            .into_iter()
-        })
-        .flatten();
-
-    let ans = lines_iter
-        .chain(cols_iter)
-        .map(|(pos, speed)| {
-            let e = map.visit(pos, speed);
-            map.reset();
-            e
-        })
-        .max()
-        .unwrap();
-
-    println!("day 16 part 2 = {ans}");
+        });
+
+        lines_iter
+            .chain(cols_iter)
+            .map(|(pos, speed)| {
+                let e = visit(&mut map, pos, speed);
+                reset(&mut map);
+                e
+            })
+            .max()
+            .unwrap()
+    }
+
+    /// Tiles energized by the part 1 beam in yellow.
+    fn render(input: &Self::Input) -> Option<Image> {
+        let mut map = input.clone();
+        visit(&mut map, Vector::zero(), Vector::new(1, 0));
+        Some(map.to_bmp(colour))
+    }
+
+    /// Tiles energized by the part 1 beam highlighted.
+    fn terminal(input: &Self::Input) -> Option<String> {
+        let mut map = input.clone();
+        visit(&mut map, Vector::zero(), Vector::new(1, 0));
+        let energized = (0..map.nodes().len()).filter(|i| map.nodes()[*i].energized());
+        let glyph = |n: &Node| match n.ty {
+            NodeType::Ground => ('.', Colour::Grey),
+            NodeType::Mirror(i) => (if i > 0 { '\\' } else { '/' }, Colour::White),
+            NodeType::Splitter(d) => (if d.x != 0 { '-' } else { '|' }, Colour::White),
+        };
+        Some(map.terminal(glyph).highlight(energized).to_string())
+    }
+
+    /// The part 1 beam spreading one tile per frame.
+    fn animate(input: &Self::Input, recorder: &mut Recorder) {
+        let mut map = input.clone();
+        recorder.record(&map, colour);
+        propagate(&mut map, Vector::zero(), Vector::new(1, 0), recorder);
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day16::part_1(&Day16::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day16::part_2(&Day16::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day16;
+    use crate::{image::Recorder, Solution, Vector};
+
+    const EXAMPLE: &str = r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(16, |input| {
+            crate::answers::check(16, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(16, |input| {
+            crate::answers::check(16, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 46);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 51);
+    }
+
+    #[test]
+    fn wide_part_2() {
+        // Only entering from the right edge gets past the splitter.
+        assert_eq!(super::part_2("|..\n").unwrap(), 3);
+    }
+
+    #[test]
+    fn propagate() {
+        let mut map = Day16::parse(EXAMPLE).unwrap();
+        let mut recorder = Recorder::new(1);
+        super::propagate(&mut map, Vector::zero(), Vector::new(1, 0), &mut recorder);
+        assert_eq!(map.nodes().iter().filter(|n| n.energized()).count(), 46);
+        assert!(recorder.frames().len() > 1);
+    }
 }
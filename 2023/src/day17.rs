@@ -1,185 +1,168 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-
-const INPUT: &str = std::include_str!("input/day17.txt");
+use crate::{
+    image::{self, Image, Pixel},
+    search::{self, Path},
+    terminal::Colour,
+    Direction, Map, ParseError, Solution, Vector,
+};
+
+/// A crucible at `pos` that moved `dir_count` times towards `dir`.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+struct Crucible {
+    pos: Vector,
+    dir: Direction,
+    dir_count: usize,
+}
 
-type Vector = euclid::Vector2D<isize, ()>;
+fn run_crucible(map: &Map<usize>, can_go: impl Fn(bool, usize) -> bool) -> Path<Crucible> {
+    // The crucible may set off right or down, when it has to go straight a
+    // while before turning only starting both ways lets it go down.
+    let starts = [Direction::Right, Direction::Down].map(|dir| Crucible {
+        pos: Vector::zero(),
+        dir,
+        dir_count: 0,
+    });
+    let exit = map.bounds() - Vector::new(1, 1);
+    let can_go = &can_go;
+    let successors = |c: &Crucible| {
+        let c = *c;
+        map.neighbours(c.pos)
+            .filter(move |(d, _, _)| *d != c.dir.opposite())
+            .filter_map(move |(d, _, loss)| {
+                let same_dir = d == c.dir;
+                can_go(same_dir, c.dir_count).then(|| {
+                    let next = Crucible {
+                        pos: c.pos + d.to_vector(),
+                        dir: d,
+                        dir_count: if same_dir { c.dir_count + 1 } else { 1 },
+                    };
+                    (next, *loss)
+                })
+            })
+    };
+    // Every block loses at least the least heat of any block, so the
+    // distance to the exit times that never overestimates, even when some
+    // blocks lose none.
+    let least = map.nodes().iter().copied().min().unwrap_or(0);
+    let heuristic = |c: &Crucible| {
+        let d = exit - c.pos;
+        (d.x + d.y) as usize * least
+    };
+    let is_exit = |c: &Crucible| c.pos == exit && can_go(false, c.dir_count);
+    search::astar(starts, successors, heuristic, is_exit).expect("no path to the exit")
+}
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
-struct Candidate {
-    distance: usize,
-    dir: Vector,
-    dir_count: usize,
+fn part_1_crucible(map: &Map<usize>) -> Path<Crucible> {
+    run_crucible(map, |same_dir, d| !same_dir || d < 3)
 }
 
-impl Ord for Candidate {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.distance.cmp(&other.distance)
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Map<usize>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Clumsy Crucible";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Map::parse(Self::DAY, input, |c| c.to_digit(10).map(|d| d as usize))
     }
-}
 
-impl PartialOrd for Candidate {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        part_1_crucible(input).cost
     }
-}
 
-struct Node {
-    value: usize,
-    state: HashMap<Vector, (usize, bool)>,
-}
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        run_crucible(input, |same_dir, d| if same_dir { d < 10 } else { d >= 4 }).cost
+    }
 
-impl Node {
-    fn new(value: usize) -> Self {
-        Self {
-            value,
-            state: HashMap::new(),
+    /// Heat loss in shades of grey, the part 1 path in red.
+    fn render(input: &Self::Input) -> Option<Image> {
+        let mut tiles = Map::new(
+            input
+                .nodes()
+                .iter()
+                .map(|loss| {
+                    let shade = (*loss * 25) as u8;
+                    Pixel::new(shade, shade, shade)
+                })
+                .collect(),
+            input.cols(),
+        );
+        for c in part_1_crucible(input).states {
+            *tiles.at_mut(c.pos).unwrap() = image::RED;
         }
+        Some(tiles.to_bmp(|p| *p))
     }
-}
 
-#[derive(Eq, PartialEq)]
-struct HeapEntry(Candidate, Vector);
-
-impl PartialOrd for HeapEntry {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    /// Heat loss digits with the part 1 path drawn over them.
+    fn terminal(input: &Self::Input) -> Option<String> {
+        let path = part_1_crucible(input).states.into_iter().map(|c| c.pos);
+        let glyph = |loss: &usize| (char::from_digit(*loss as u32, 10).unwrap(), Colour::Grey);
+        Some(input.terminal(glyph).path(path).to_string())
     }
 }
 
-impl Ord for HeapEntry {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.0.cmp(&other.0).reverse()
-    }
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day17::part_1(&Day17::parse(input)?))
 }
 
-struct Map {
-    nodes: Vec<Node>,
-    cols: usize,
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day17::part_2(&Day17::parse(input)?))
 }
 
-impl Map {
-    fn new(s: &str) -> Self {
-        s.lines()
-            .fold(None, |mut map, l| {
-                let m = map.get_or_insert_with(|| Map {
-                    nodes: Vec::new(),
-                    cols: l.len(),
-                });
-                m.nodes.extend(
-                    l.chars()
-                        .map(|c| Node::new(c.to_digit(10).unwrap() as usize)),
-                );
-                map
-            })
-            .unwrap()
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(17, |input| {
+            crate::answers::check(17, 1, super::part_1(input).unwrap());
+        });
     }
 
-    fn at(&mut self, Vector { x, y, .. }: Vector) -> Option<&mut Node> {
-        let x = usize::try_from(x).ok()?;
-        if x >= self.cols {
-            return None;
-        }
-        let y = usize::try_from(y).ok()?;
-        self.nodes.get_mut(y * self.cols + x)
+    #[test]
+    fn part_2() {
+        crate::input::with_input(17, |input| {
+            crate::answers::check(17, 2, super::part_2(input).unwrap());
+        });
     }
 
-    fn lines(&self) -> usize {
-        self.nodes.len() / self.cols
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 102);
     }
 
-    fn exit(&self) -> Vector {
-        let x = self.cols as isize;
-        let y = self.lines() as isize;
-        Vector::new(x - 1, y - 1)
+    #[test]
+    fn free_blocks() {
+        // Right along the free top row and down the right side loses 1.
+        assert_eq!(super::part_1("500\n010\n011\n").unwrap(), 1);
     }
-}
-
-fn run_crucible(input: &str, can_go: impl Fn(bool, usize) -> bool) -> usize {
-    let mut map = Map::new(input);
-    let mut heap = BinaryHeap::new();
-    heap.push(HeapEntry(
-        Candidate {
-            distance: 0,
-            dir: Vector::new(1, 0),
-            dir_count: 0,
-        },
-        Vector::new(0, 0),
-    ));
-    let directions = [
-        Vector::new(1, 0),
-        Vector::new(-1, 0),
-        Vector::new(0, -1),
-        Vector::new(0, 1),
-    ];
-    let exit = map.exit();
-    let mut ans = usize::MAX;
-    while let Some(HeapEntry(candidate, pos)) = heap.pop() {
-        let candidate_key = candidate.dir * (candidate.dir_count as isize);
-        let (cur_val, cur_visited) = map
-            .at(pos)
-            .unwrap()
-            .state
-            .entry(candidate_key)
-            .or_insert((usize::MAX, false));
-        if std::mem::replace(cur_visited, true) {
-            continue;
-        }
 
-        if pos == exit {
-            if *cur_val < ans && can_go(false, candidate.dir_count) {
-                ans = *cur_val;
-            }
-            continue;
-        }
-
-        for d in directions.iter().copied() {
-            let same_dir = match d.dot(candidate.dir) {
-                -1 => continue,
-                0 => false,
-                1 => true,
-                o => panic!("bad dot {d:?}, {candidate:?}, {o}"),
-            };
-            if !can_go(same_dir, candidate.dir_count) {
-                continue;
-            }
-            let consider = pos + d;
-            let node = if let Some(n) = map.at(consider) {
-                n
-            } else {
-                continue;
-            };
-            let tentative = Candidate {
-                distance: candidate.distance + node.value,
-                dir: d,
-                dir_count: same_dir.then_some(candidate.dir_count + 1).unwrap_or(1),
-            };
-            let tentative_key = tentative.dir * (tentative.dir_count as isize);
-            let (best, visited) = node
-                .state
-                .entry(tentative_key)
-                .or_insert((usize::MAX, false));
-            if *visited {
-                continue;
-            }
-            if *best < tentative.distance {
-                continue;
-            }
-            *best = tentative.distance;
-            heap.push(HeapEntry(tentative, consider));
-        }
+    #[test]
+    fn down_first() {
+        // A single column only lets the ultra crucible set off downwards.
+        assert_eq!(super::part_2("1\n1\n1\n1\n1\n").unwrap(), 4);
     }
-    ans
-}
 
-#[test]
-fn part_1() {
-    let ans = run_crucible(INPUT, |same_dir, d| !same_dir || d < 3);
-    println!("day 17 part 1 = {ans}");
-}
-
-#[test]
-fn part_2() {
-    let ans = run_crucible(INPUT, |same_dir, d| if same_dir { d < 10 } else { d >= 4 });
-    println!("day 17 part 2 = {ans}");
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 94);
+    }
 }
@@ -1,8 +1,4 @@
-use std::str::FromStr;
-
-const INPUT: &str = std::include_str!("input/day18.txt");
-
-type Vector = euclid::Vector2D<isize, ()>;
+use crate::{parse, Direction, ParseError, Solution, Vector};
 
 fn area(iter: impl Iterator<Item = Vector>) -> isize {
     let (_, inner, perimeter) = iter.fold((Vector::zero(), 0, 0), |(p0, inner, perimeter), v| {
@@ -16,40 +12,90 @@ fn area(iter: impl Iterator<Item = Vector>) -> isize {
     isize::abs(inner + perimeter / 2 + 1)
 }
 
-#[test]
-fn part_1() {
-    let iter = INPUT.lines().map(|l| {
-        let mut l = l.split(" ");
-        let dir = match l.next().unwrap() {
-            "R" => Vector::new(1, 0),
-            "L" => Vector::new(-1, 0),
-            "U" => Vector::new(0, -1),
-            "D" => Vector::new(0, 1),
-            o => panic!("unknown dir {o}"),
-        };
-        let sz = isize::from_str(l.next().unwrap()).expect("bad number");
-        dir * sz
-    });
-    let ans = area(iter);
-    println!("day 18 part 1 = {ans}");
+pub struct Day18;
+
+impl Solution for Day18 {
+    /// Each line as the dig vector for both parts.
+    type Input = Vec<(Vector, Vector)>;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "Lavaduct Lagoon";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let regex = regex::Regex::new(r"^([A-Z]) ([0-9]+) \(#([0-9a-f]{5})([0-9])\)$").unwrap();
+        parse::lines(Self::DAY, input)
+            .map(|l| {
+                let captures = l.captures(&regex)?;
+                let group = |i| captures.get(i).unwrap().as_str();
+                let dir = l.parse::<Direction>(group(1))?.to_vector();
+                let sz = l.parse::<isize>(group(2))?;
+
+                let color_dir = l.parse::<Direction>(group(4))?.to_vector();
+                let color_sz = isize::from_str_radix(group(3), 16).unwrap();
+                Ok((dir * sz, color_dir * color_sz))
+            })
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        area(input.iter().map(|(v, _)| *v))
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        area(input.iter().map(|(_, v)| *v))
+    }
 }
 
-#[test]
-fn part_2() {
-    let iter = INPUT.lines().map(|l| {
-        let l = l.split(" ").last().unwrap().as_bytes();
-        assert_eq!(l.len(), 9);
-        let hex = &l[2..7];
-        let dir = match l[7] as char {
-            '0' => Vector::new(1, 0),
-            '2' => Vector::new(-1, 0),
-            '3' => Vector::new(0, -1),
-            '1' => Vector::new(0, 1),
-            o => panic!("unknown dir {o}"),
-        };
-        let sz = usize::from_str_radix(String::from_utf8_lossy(hex).as_ref(), 16).unwrap();
-        dir * (sz as isize)
-    });
-    let ans = area(iter);
-    println!("day 18 part 2 = {ans}");
+pub fn part_1(input: &str) -> Result<isize, ParseError> {
+    Ok(Day18::part_1(&Day18::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<isize, ParseError> {
+    Ok(Day18::part_2(&Day18::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(18, |input| {
+            crate::answers::check(18, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(18, |input| {
+            crate::answers::check(18, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 62);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 952408144115);
+    }
 }
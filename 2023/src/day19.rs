@@ -1,9 +1,8 @@
 use std::{collections::HashMap, str::FromStr};
 
 use itertools::{FoldWhile, Itertools};
-use std::ops::Range;
 
-const INPUT: &str = std::include_str!("input/day19.txt");
+use crate::{parse, IntervalSet, ParseError, Solution};
 
 #[derive(Debug, Clone)]
 enum Ruling {
@@ -31,64 +30,93 @@ enum Condition {
 }
 
 #[derive(Debug)]
-struct Rule {
+pub struct Rule {
     cond: Option<Condition>,
     target: Ruling,
 }
 
-fn parse_rules<'a, I: Iterator<Item = &'a str>>(lines: I) -> HashMap<String, Vec<Rule>> {
-    let parts_regex = regex::Regex::new(r"([a-z]+)\{(.*)\}").unwrap();
-    let rule_regex = regex::Regex::new(r"(?:([a-z])([<>])([0-9]+):)?([ARa-z]+)").unwrap();
+fn parse_rules(lines: &mut parse::Lines) -> Result<HashMap<String, Vec<Rule>>, ParseError> {
+    let parts_regex = regex::Regex::new(r"^([a-z]+)\{(.*)\}$").unwrap();
+    let rule_regex = regex::Regex::new(r"^(?:([xmas])([<>])([0-9]+):)?([ARa-z]+)$").unwrap();
+    let mut map = HashMap::new();
+    let mut lookups = Vec::new();
+    for l in lines.by_ref() {
+        if l.text.is_empty() {
+            break;
+        }
+        let captures = l.captures(&parts_regex)?;
+        let key = captures.get(1).unwrap().as_str();
+        let rules = captures.get(2).unwrap().as_str();
+        let rules = rules
+            .split(",")
+            .map(|r| {
+                let captures = rule_regex
+                    .captures(r)
+                    .ok_or_else(|| l.error(r, format!("doesn't match {rule_regex}")))?;
+                let target = captures.get(4).unwrap().as_str();
+                lookups.push((l, target));
+                let target = Ruling::from_str(target).unwrap();
+                let cond = match (captures.get(1), captures.get(2), captures.get(3)) {
+                    (Some(c), Some(s), Some(v)) => {
+                        let c = c.as_str().chars().next().unwrap();
+                        let v = l.parse(v.as_str())?;
+                        Some(match s.as_str() {
+                            ">" => Condition::Gt(c, v),
+                            _ => Condition::Lt(c, v),
+                        })
+                    }
+                    _ => None,
+                };
+                Ok(Rule { cond, target })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if map.insert(key.to_string(), rules).is_some() {
+            return Err(l.error(key, "duplicate workflow"));
+        }
+    }
+    if !map.contains_key("in") {
+        return Err(lines.end_error("no \"in\" workflow"));
+    }
+    for (l, target) in lookups {
+        if let Ruling::Lookup(k) = Ruling::from_str(target).unwrap() {
+            if !map.contains_key(&k) {
+                return Err(l.error(target, format!("no workflow {k}")));
+            }
+        }
+    }
+    Ok(map)
+}
+
+fn parse_parts(lines: parse::Lines) -> Result<Vec<HashMap<char, usize>>, ParseError> {
+    let value_regex = regex::Regex::new(r"^([xmas])=([0-9]+)$").unwrap();
     lines
-        .take_while(|l| !l.is_empty())
-        .fold(HashMap::new(), |mut map, l| {
-            let captures = parts_regex.captures(l).expect("no captures");
-            let key = captures.get(1).unwrap().as_str().to_string();
-            let rules = captures.get(2).unwrap().as_str();
-            let rules = rules
+        .map(|l| {
+            let values = l
+                .text
+                .strip_prefix("{")
+                .and_then(|v| v.strip_suffix("}"))
+                .ok_or_else(|| l.error(l.text, "expected {...}"))?;
+            let part = values
                 .split(",")
-                .map(|r| {
-                    let captures = rule_regex
-                        .captures(r)
-                        .unwrap_or_else(|| panic!("{r} rule doesn't match"));
-                    let target =
-                        Ruling::from_str(captures.get(4).expect("no target").as_str()).unwrap();
-                    let cond = match (captures.get(1), captures.get(2), captures.get(3)) {
-                        (Some(c), Some(s), Some(v)) => {
-                            let c = c.as_str().chars().next().unwrap();
-                            let v = usize::from_str(v.as_str()).unwrap();
-                            Some(match s.as_str() {
-                                ">" => Condition::Gt(c, v),
-                                "<" => Condition::Lt(c, v),
-                                o => panic!("unknown condition {o}"),
-                            })
-                        }
-                        (None, None, None) => None,
-                        o => panic!("bad captures {o:?} for {r}"),
-                    };
-                    Rule { cond, target }
+                .map(|v| {
+                    let captures = value_regex
+                        .captures(v)
+                        .ok_or_else(|| l.error(v, format!("doesn't match {value_regex}")))?;
+                    let k = captures.get(1).unwrap().as_str().chars().next().unwrap();
+                    let v = l.parse(captures.get(2).unwrap().as_str())?;
+                    Ok((k, v))
                 })
-                .collect::<Vec<_>>();
-            assert!(map.insert(key, rules).is_none());
-            map
+                .collect::<Result<HashMap<_, _>, _>>()?;
+            match "xmas".chars().find(|c| !part.contains_key(c)) {
+                Some(c) => Err(l.error(l.text, format!("missing {c}"))),
+                None => Ok(part),
+            }
         })
+        .collect()
 }
 
-#[test]
-fn part_1() {
-    let mut lines = INPUT.lines();
-    let rules = parse_rules(lines.by_ref());
-    let ans = lines.fold(0, |sum, l| {
-        let l = &l[1..l.len() - 1];
-        let values = l
-            .split(",")
-            .map(|l| {
-                let (k, v) = l.split_once("=").unwrap();
-                let v = usize::from_str(v).unwrap();
-                (k.chars().next().unwrap(), v)
-            })
-            .collect::<HashMap<_, _>>();
-
+fn accepted(rules: &HashMap<String, Vec<Rule>>, parts: &[HashMap<char, usize>]) -> usize {
+    parts.iter().fold(0, |sum, values| {
         let mut key = "in".to_string();
         loop {
             let rules = rules.get(&key).unwrap_or_else(|| panic!("no key {key}"));
@@ -118,14 +146,13 @@ fn part_1() {
                 }
             }
         }
-    });
-    println!("day 19 part 1 = {ans}");
+    })
 }
 
 fn count_combinations(
     rules: &HashMap<String, Vec<Rule>>,
     key: &str,
-    ranges: HashMap<char, Range<usize>>,
+    ranges: HashMap<char, IntervalSet<usize>>,
 ) -> usize {
     if ranges.values().any(|r| r.is_empty()) {
         return 0;
@@ -140,36 +167,35 @@ fn count_combinations(
                 let mut inner_state = state.clone();
                 match cond {
                     Condition::Gt(c, v) => {
-                        let range = state.get_mut(c).unwrap();
-                        range.end = *v + 1;
-                        let range = inner_state.get_mut(c).unwrap();
-                        range.start = *v + 1;
+                        let (below, above) = state[c].split_at(*v + 1);
+                        state.insert(*c, below);
+                        inner_state.insert(*c, above);
                     }
                     Condition::Lt(c, v) => {
-                        let range = state.get_mut(c).unwrap();
-                        range.start = *v;
-                        let range = inner_state.get_mut(c).unwrap();
-                        range.end = *v;
+                        let (below, above) = state[c].split_at(*v);
+                        state.insert(*c, above);
+                        inner_state.insert(*c, below);
                     }
                 }
 
                 let inner_sum = match target {
-                    Ruling::Accept => inner_state.values().map(|r| r.len()).product::<usize>(),
+                    Ruling::Accept => inner_state
+                        .values()
+                        .map(IntervalSet::len)
+                        .product::<usize>(),
                     Ruling::Reject => 0,
-                    Ruling::Lookup(k) => count_combinations(rules, &k, inner_state),
+                    Ruling::Lookup(k) => count_combinations(rules, k, inner_state),
                 };
 
                 FoldWhile::Continue((sum + inner_sum, state))
             } else {
                 FoldWhile::Done(match target {
                     Ruling::Accept => (
-                        sum + state.values().map(|r| r.len()).product::<usize>(),
+                        sum + state.values().map(IntervalSet::len).product::<usize>(),
                         state,
                     ),
                     Ruling::Reject => (sum, state),
-                    Ruling::Lookup(k) => {
-                        (sum + count_combinations(rules, &k, state.clone()), state)
-                    }
+                    Ruling::Lookup(k) => (sum + count_combinations(rules, k, state.clone()), state),
                 })
             }
         })
@@ -177,13 +203,108 @@ fn count_combinations(
     sum
 }
 
-#[test]
-fn part_2() {
-    let rules = parse_rules(INPUT.lines());
-    let initial_ranges = ['s', 'm', 'a', 'x']
-        .into_iter()
-        .map(|c| (c, 1..4001))
-        .collect::<HashMap<_, _>>();
-    let ans = count_combinations(&rules, "in", initial_ranges);
-    println!("day 19 part 2 = {ans}");
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = (HashMap<String, Vec<Rule>>, Vec<HashMap<char, usize>>);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Aplenty";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = parse::lines(Self::DAY, input);
+        let rules = parse_rules(&mut lines)?;
+        let parts = parse_parts(lines)?;
+        Ok((rules, parts))
+    }
+
+    fn part_1((rules, parts): &Self::Input) -> Self::Part1 {
+        accepted(rules, parts)
+    }
+
+    fn part_2((rules, _): &Self::Input) -> Self::Part2 {
+        let initial_ranges = ['s', 'm', 'a', 'x']
+            .into_iter()
+            .map(|c| (c, IntervalSet::from(1..4001)))
+            .collect::<HashMap<_, _>>();
+        count_combinations(rules, "in", initial_ranges)
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day19::part_1(&Day19::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day19::part_2(&Day19::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(19, |input| {
+            crate::answers::check(19, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(19, |input| {
+            crate::answers::check(19, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 19114);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 167409079868000);
+    }
+
+    #[test]
+    fn unknown_workflow() {
+        let e = super::part_1("in{x>10:foo,A}\n\n{x=1,m=2,a=3,s=4}\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 9));
+        assert_eq!(e.message, "no workflow foo");
+    }
+
+    #[test]
+    fn no_in_workflow() {
+        let e = super::part_1("px{x>10:R,A}\n\n{x=1,m=2,a=3,s=4}\n").unwrap_err();
+        assert_eq!((e.line, e.column), (4, 1));
+        assert_eq!(e.message, "no \"in\" workflow");
+    }
+
+    #[test]
+    fn missing_rating() {
+        let e = super::part_1("in{x>10:R,A}\n\n{x=1,m=2,s=4}\n").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+        assert_eq!(e.message, "missing a");
+    }
 }
@@ -1,7 +1,8 @@
 use itertools::{FoldWhile, Itertools};
-use regex::Regex;
 use std::{cmp, str::FromStr};
 
+use crate::{parse, ParseError, Solution};
+
 enum Color {
     Red,
     Green,
@@ -21,97 +22,141 @@ impl FromStr for Color {
     }
 }
 
-const INPUT: &str = std::include_str!("input/day2.txt");
-
-#[test]
-fn part_1() {
-    let search = Regex::new("(?:Game ([0-9]+):)? ([0-9]+) ([a-z]+)([,;])?").unwrap();
-    let lines = INPUT.lines();
-    let ans: usize = lines
-        .map(|l| {
-            let r = search
-                .captures_iter(&l)
-                .map(|capture| {
-                    let _ = capture.get(0).expect("no match");
-                    let game = capture
-                        .get(1)
-                        .map(|game| usize::from_str_radix(game.as_str(), 10).expect("parse game"));
-                    let count =
-                        usize::from_str_radix(capture.get(2).expect("capture count").as_str(), 10)
-                            .expect("parse count");
-                    let color = Color::from_str(capture.get(3).expect("capture color").as_str())
-                        .expect("color");
-                    let end = capture.get(4).map(|m| m.as_str() == ";").unwrap_or(true);
-                    (game, count, color, end)
-                })
-                .fold_while(
-                    (None, 0, 0, 0),
-                    |(cur_game, mut red, mut green, mut blue), (game, count, color, end)| {
-                        let game = match (cur_game, game) {
-                            (None, Some(g)) | (Some(g), None) => Some(g),
-                            (cur, game) => panic!("{cur:?} {game:?}"),
-                        };
+pub struct Game {
+    id: usize,
+    /// Cubes drawn and whether the draw ends a set.
+    draws: Vec<(usize, Color, bool)>,
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Game>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Cube Conundrum";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(Self::DAY, input)
+            .map(|l| {
+                let (game, sets) = l.split_once(l.text, ":")?;
+                let id = game
+                    .strip_prefix("Game ")
+                    .ok_or_else(|| l.error(game, "expected \"Game\""))?;
+                let id = l.parse(id)?;
+                let mut draws = Vec::new();
+                for set in sets.split(";") {
+                    let cubes = set.split(",").collect::<Vec<_>>();
+                    for (i, cube) in cubes.iter().enumerate() {
+                        let (count, color) = l.split_once(cube.trim(), " ")?;
+                        let end = i + 1 == cubes.len();
+                        draws.push((l.parse(count)?, l.parse(color)?, end));
+                    }
+                }
+                Ok(Game { id, draws })
+            })
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        input
+            .iter()
+            .map(|Game { id, draws }| {
+                let r = draws.iter().fold_while(
+                    (0, 0, 0),
+                    |(mut red, mut green, mut blue), (count, color, end)| {
                         match color {
                             Color::Red => red += count,
                             Color::Blue => blue += count,
                             Color::Green => green += count,
                         }
                         if red > 12 || green > 13 || blue > 14 {
-                            return FoldWhile::Done((None, 0, 0, 0));
+                            return FoldWhile::Done((0, 0, 0));
                         }
-                        if end {
+                        if *end {
                             red = 0;
                             blue = 0;
                             green = 0;
                         }
-                        FoldWhile::Continue((game, red, green, blue))
+                        FoldWhile::Continue((red, green, blue))
                     },
                 );
-            let partial = match r {
-                FoldWhile::Done(_) => 0,
-                FoldWhile::Continue((game, _, _, _)) => game.expect("no game at end"),
-            };
-
-            println!("{l} => {partial}");
-            partial
-        })
-        .sum();
-    println!("day 2 part 1 = {ans}");
-}
+                let partial = match r {
+                    FoldWhile::Done(_) => 0,
+                    FoldWhile::Continue(_) => *id,
+                };
 
-#[test]
-fn part_2() {
-    let search = Regex::new("(?:Game ([0-9]+):)? ([0-9]+) ([a-z]+)([,;])?").unwrap();
-    let lines = INPUT.lines();
-    let ans: usize = lines
-        .map(|l| {
-            let colors = search
-                .captures_iter(&l)
-                .map(|capture| {
-                    let _ = capture.get(0).expect("no match");
-                    let count =
-                        usize::from_str_radix(capture.get(2).expect("capture count").as_str(), 10)
-                            .expect("parse count");
-                    let color = Color::from_str(capture.get(3).expect("capture color").as_str())
-                        .expect("color");
-                    (count, color)
-                })
-                .fold(
+                log::trace!("game {id} => {partial}");
+                partial
+            })
+            .sum()
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        input
+            .iter()
+            .map(|Game { id, draws }| {
+                let colors = draws.iter().fold(
                     (0, 0, 0),
-                    |(mut red, mut green, mut blue), (count, color)| {
+                    |(mut red, mut green, mut blue), (count, color, _)| {
                         let target = match color {
                             Color::Red => &mut red,
                             Color::Blue => &mut blue,
                             Color::Green => &mut green,
                         };
-                        *target = cmp::max(*target, count);
+                        *target = cmp::max(*target, *count);
                         (red, green, blue)
                     },
                 );
-            println!("{l} => {colors:?}");
-            let (r, g, b) = colors;
-            r * g * b
-        })
-        .sum();
-    println!("day 2 part 2 = {ans}");
+                log::trace!("game {id} => {colors:?}");
+                let (r, g, b) = colors;
+                r * g * b
+            })
+            .sum()
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day2::part_1(&Day2::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day2::part_2(&Day2::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(2, |input| {
+            crate::answers::check(2, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(2, |input| {
+            crate::answers::check(2, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 8);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 2286);
+    }
 }
@@ -1,52 +1,61 @@
 use std::collections::{BTreeMap, HashMap};
 
-const INPUT: &str = std::include_str!("input/day20.txt");
+use itertools::Itertools;
 
-#[derive(Debug)]
+use crate::{cycle, parse, ParseError, Solution};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum Op {
     FlipFlop(bool),
     Conj(BTreeMap<String, bool>),
     None,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Node {
     op: Op,
     outputs: Vec<String>,
 }
 
-#[derive(Debug)]
-struct Circuit {
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Circuit {
     nodes: BTreeMap<String, Node>,
 }
 
 impl Circuit {
-    fn new(input: &str) -> Self {
-        let rx = regex::Regex::new(r"([&%])?([a-z]+) -> (.*)").unwrap();
-        let mut nodes = input.lines().fold(BTreeMap::new(), |mut map, l| {
-            let captures = rx.captures(l).unwrap();
+    fn new(day: u8, input: &str) -> Result<Self, ParseError> {
+        let rx = regex::Regex::new(r"^([&%])?([a-z]+) -> ([a-z]+(?:, [a-z]+)*)$").unwrap();
+        let mut nodes = BTreeMap::new();
+        for l in parse::lines(day, input) {
+            let captures = l.captures(&rx)?;
             let op = match captures.get(1).map(|m| m.as_str()) {
                 None => Op::None,
                 Some("%") => Op::FlipFlop(false),
-                Some("&") => Op::Conj(BTreeMap::new()),
-                o => panic!("bad capture {o:?}"),
+                Some(_) => Op::Conj(BTreeMap::new()),
             };
-            let key = captures.get(2).unwrap().as_str().to_string();
+            let key = captures.get(2).unwrap().as_str();
             let outputs = captures
                 .get(3)
                 .unwrap()
                 .as_str()
                 .split(", ")
-                .map(|s| s.to_string())
-                .collect();
+                .collect::<Vec<_>>();
+            if let Some(dup) = outputs.iter().duplicates().next() {
+                return Err(l.error(dup, "duplicate output"));
+            }
+            let outputs = outputs.into_iter().map(|s| s.to_string()).collect();
 
-            assert!(map.insert(key, Node { op, outputs }).is_none());
-            map
-        });
+            if nodes
+                .insert(key.to_string(), Node { op, outputs })
+                .is_some()
+            {
+                return Err(l.error(key, "duplicate module"));
+            }
+        }
         // Update the conjunction module's inputs.
         let conjunctions = nodes
             .iter()
-            .map(|(src, n)| {
+            .flat_map(|(src, n)| {
                 n.outputs.iter().filter_map(|dst| {
                     nodes.get(dst).and_then(|n| match n.op {
                         Op::Conj(_) => Some((src.clone(), dst.clone())),
@@ -54,17 +63,16 @@ impl Circuit {
                     })
                 })
             })
-            .flatten()
             .collect::<Vec<_>>();
         for (src, dst) in conjunctions {
             match &mut nodes.get_mut(&dst).as_mut().unwrap().op {
                 Op::Conj(inputs) => {
-                    assert!(inputs.insert(src, false).is_none());
+                    inputs.insert(src, false);
                 }
                 _ => panic!("should be conj"),
             }
         }
-        Self { nodes }
+        Ok(Self { nodes })
     }
 
     fn run_pulses(
@@ -102,107 +110,177 @@ impl Circuit {
         )
     }
 
-    fn state(&self) -> Vec<bool> {
-        self.nodes
-            .values()
-            .filter_map(|Node { op, outputs: _ }| match op {
-                Op::FlipFlop(f) => Some(itertools::Either::Left(std::iter::once(*f))),
-                Op::Conj(s) => Some(itertools::Either::Right(s.values().copied())),
-                Op::None => None,
-            })
-            .flatten()
-            .collect()
-    }
-}
-
-#[test]
-fn part_1() {
-    let mut circuit = Circuit::new(INPUT);
-    let mut mem = HashMap::<Vec<bool>, (usize, usize, Vec<bool>)>::new();
-    let mut state = circuit.state();
-    let mut lo = 0;
-    let mut hi = 0;
-    for _ in 0..1000 {
-        let mem_entry = match mem.entry(state) {
-            std::collections::hash_map::Entry::Occupied(o) => {
-                let (l, h, n) = o.get();
-                lo += *l;
-                hi += *h;
-                state = n.clone();
-                continue;
-            }
-            std::collections::hash_map::Entry::Vacant(v) => v,
-        };
+    /// Pushes the button, returns the low and high pulses sent.
+    fn press(&mut self) -> (usize, usize) {
         let mut pulses = vec![(false, "button".to_string(), "broadcaster".to_string())];
-        let mut rl = 0;
-        let mut rh = 0;
+        let mut lo = 0;
+        let mut hi = 0;
         while !pulses.is_empty() {
-            let (l, h, v) = circuit.run_pulses(pulses.into_iter());
-            rl += l;
-            rh += h;
+            let (l, h, v) = self.run_pulses(pulses.into_iter());
+            lo += l;
+            hi += h;
             pulses = v;
         }
-        state = circuit.state();
-        mem_entry.insert((rl, rh, state.clone()));
-        lo += rl;
-        hi += rh;
+        (lo, hi)
     }
-
-    let ans = lo * hi;
-    println!("day 20 part 1 = {lo} * {hi} = {ans}");
 }
 
-#[test]
-fn part_2() {
-    let mut circuit = Circuit::new(INPUT);
-
-    // There is only one thing that outputs to rx find it.
-    //
-    // This would not work if there was a flip-flop in front of rx or something
-    // like that because we want it to be a `Conj`. A more general solution
-    // would calculate the cycles for everything.
-    let look = circuit
-        .nodes
-        .iter()
-        .filter_map(|(k, n)| {
-            n.outputs
-                .iter()
-                .find_map(|o| (o == "rx").then_some(k.to_string()))
-        })
-        .collect::<Vec<_>>();
-    assert_eq!(look.len(), 1);
-    let look = look.into_iter().next().unwrap();
-    let want_sources = match &circuit.nodes.get(&look).unwrap().op {
-        Op::Conj(inputs) => inputs.len(),
-        _ => panic!("must be conj"),
-    };
-    let mut sources = HashMap::new();
-    let mut presses = 0usize;
-    loop {
-        presses += 1;
-        let mut pulses = vec![(false, "button".to_string(), "broadcaster".to_string())];
-        while !pulses.is_empty() {
-            let (_, _, v) = circuit.run_pulses(pulses.into_iter());
-            for (pulse, src, target) in v.iter() {
-                if *pulse && target == &look {
-                    sources
-                        .entry(src.to_string())
-                        .or_insert(Vec::new())
-                        .push(presses);
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Circuit;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Pulse Propagation";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Circuit::new(Self::DAY, input)
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        let presses = 1000;
+        // The pulses sent only depend on the state, only press the button
+        // until it repeats.
+        let cycle = cycle::find_within(
+            input,
+            |c| {
+                c.press();
+            },
+            presses,
+        );
+        let distinct = cycle.map_or(presses, |c| c.prefix_len + c.cycle_len);
+        let mut circuit = input.clone();
+        let sent = (0..distinct).map(|_| circuit.press()).collect::<Vec<_>>();
+        let (lo, hi) = (0..presses)
+            .map(|n| sent[cycle.map_or(n, |c| c.reduce(n))])
+            .fold((0, 0), |(lo, hi), (l, h)| (lo + l, hi + h));
+        lo * hi
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let mut circuit = input.clone();
+
+        // There is only one thing that outputs to rx find it.
+        //
+        // This would not work if there was a flip-flop in front of rx or something
+        // like that because we want it to be a `Conj`. A more general solution
+        // would calculate the cycles for everything.
+        let look = circuit
+            .nodes
+            .iter()
+            .filter_map(|(k, n)| {
+                n.outputs
+                    .iter()
+                    .find_map(|o| (o == "rx").then_some(k.to_string()))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(look.len(), 1);
+        let look = look.into_iter().next().unwrap();
+        let want_sources = match &circuit.nodes.get(&look).unwrap().op {
+            Op::Conj(inputs) => inputs.len(),
+            _ => panic!("must be conj"),
+        };
+        let mut sources = HashMap::new();
+        let mut presses = 0usize;
+        loop {
+            presses += 1;
+            let mut pulses = vec![(false, "button".to_string(), "broadcaster".to_string())];
+            while !pulses.is_empty() {
+                let (_, _, v) = circuit.run_pulses(pulses.into_iter());
+                for (pulse, src, target) in v.iter() {
+                    // Only the first high counts, a short cycle can fire
+                    // again before the longest one fires at all.
+                    if *pulse && target == &look {
+                        sources.entry(src.to_string()).or_insert(presses);
+                    }
                 }
+                pulses = v;
+            }
+            if sources.len() == want_sources {
+                break;
             }
-            pulses = v;
-        }
-        if sources.len() == want_sources {
-            break;
         }
+
+        sources.into_values().fold(1, num::integer::lcm)
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day20::part_1(&Day20::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day20::part_2(&Day20::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE_1: &str = "\
+broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+";
+    const EXAMPLE_2: &str = "\
+broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(20, |input| {
+            crate::answers::check(20, 1, super::part_1(input).unwrap());
+        });
     }
 
-    let ans = sources
-        .values()
-        .map(|v| v.iter().copied())
-        .flatten()
-        .fold(1, num::integer::lcm);
+    #[test]
+    fn part_2() {
+        crate::input::with_input(20, |input| {
+            crate::answers::check(20, 2, super::part_2(input).unwrap());
+        });
+    }
 
-    println!("day 20 part 2 = {ans}");
+    #[test]
+    fn example_1_part_1() {
+        assert_eq!(super::part_1(EXAMPLE_1).unwrap(), 32000000);
+    }
+
+    #[test]
+    fn example_2_part_1() {
+        assert_eq!(super::part_1(EXAMPLE_2).unwrap(), 11687500);
+    }
+
+    // Part 2 has no example, it needs a module feeding `rx`. Two counters
+    // like the real inputs, `a` counts to 1 and `b`, `d` to 3, so `i` sends
+    // high every press and `j` every third.
+    const COUNTERS: &str = "\
+broadcaster -> a, b
+%a -> c
+&c -> a, i
+&i -> z
+%b -> d, e
+%d -> e
+&e -> b, j
+&j -> z
+&z -> rx
+";
+
+    #[test]
+    fn counters_part_2() {
+        assert_eq!(super::part_2(COUNTERS).unwrap(), 3);
+    }
+
+    #[test]
+    fn duplicate_output() {
+        use crate::Solution;
+
+        let e = super::Day20::parse("broadcaster -> a, a\n&a -> b\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 19, "a"));
+    }
 }
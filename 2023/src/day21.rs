@@ -1,136 +1,242 @@
-use crate::{Direction, Map};
-use std::collections::{HashMap, VecDeque};
-
-const INPUT: &str = std::include_str!("input/day21.txt");
-
-struct Node {
+use crate::{
+    image::{self, Image, Pixel, Recorder},
+    parse,
+    search::{self, GridReached},
+    Direction, Map, ParseError, Solution, Vector,
+};
+
+#[derive(Clone)]
+pub struct Node {
     available: bool,
-    candidate_for_generation: Option<usize>,
+    start: bool,
 }
 
 impl Node {
-    fn new(c: char) -> Self {
-        let (available, candidate_for_generation) = match c {
-            '.' => (true, None),
-            '#' => (false, None),
-            'S' => (true, Some(0)),
-            o => panic!("unexpected char {o}"),
+    fn new(c: char) -> Option<Self> {
+        let (available, start) = match c {
+            '.' => (true, false),
+            '#' => (false, false),
+            'S' => (true, true),
+            _ => return None,
         };
-        Self {
-            available,
-            candidate_for_generation,
-        }
+        Some(Self { available, start })
     }
 }
 
-fn input(str: &str) -> Map<Node> {
-    let cols = str.lines().next().unwrap().len();
-    let v = str
-        .lines()
-        .map(|l| l.chars().map(|c| Node::new(c)))
-        .flatten()
-        .collect();
-    Map::new(v, cols)
+fn input(day: u8, str: &str) -> Result<Map<Node>, ParseError> {
+    let map = Map::parse(day, str, Node::new)?;
+    if !map.nodes().iter().any(|n| n.start) {
+        return Err(parse::lines(day, str).end_error("no start in map"));
+    }
+    Ok(map)
 }
 
-#[test]
-fn part_1() {
-    let mut map = input(INPUT);
-    let mut queue = VecDeque::new();
-    let start = map
-        .nodes
+/// Distances from the start within the map, up to `max_depth`.
+fn distances(map: &Map<Node>, max_depth: Option<usize>) -> GridReached {
+    let converter = map.converter();
+    let start = converter.to_idx(start(map)).unwrap();
+    search::grid_bfs(
+        map.nodes().len(),
+        [start],
+        |&i| {
+            converter
+                .neighbours(converter.to_vector(i), Direction::all())
+                .filter(|(_, n)| map.nodes()[*n].available)
+                .map(|(_, n)| n)
+        },
+        max_depth,
+    )
+}
+
+/// Plots reachable in exactly `steps`, going back and forth reaches every
+/// plot at most `steps` away with the same parity.
+fn reachable(map: &Map<Node>, steps: usize) -> usize {
+    distances(map, Some(steps))
         .iter()
-        .enumerate()
-        .find_map(|(i, n)| n.candidate_for_generation.as_ref().map(|_| i))
-        .unwrap();
-    queue.push_back(map.converter().to_vector(start));
-
-    let mut cur_generation = 0;
-    while let Some(i) = queue.pop_front() {
-        let i_gen = map.at(i).unwrap().candidate_for_generation.unwrap();
-        if i_gen == 64 {
-            break;
-        }
-        if i_gen == cur_generation {
-            cur_generation = i_gen + 1;
-        }
+        .filter(|(_, d)| d % 2 == steps % 2)
+        .count()
+}
 
-        for d in Direction::all() {
-            let d = d.to_vector();
-            let candidate = i + d;
-            let node = if let Some(c) = map.at_mut(candidate) {
-                c
-            } else {
-                continue;
-            };
-            if !node.available {
-                continue;
-            }
-            let gen = std::mem::replace(&mut node.candidate_for_generation, Some(cur_generation));
-            if gen.map(|g| g != cur_generation).unwrap_or(true) {
-                queue.push_back(candidate);
-            }
-        }
-    }
+fn start(map: &Map<Node>) -> Vector {
+    let start = map.nodes().iter().position(|n| n.start).unwrap();
+    map.converter().to_vector(start)
+}
 
-    let ans = queue.len() + 1;
-    println!("day 21 part 1 = {ans}");
+/// Plots reachable in exactly `steps` on the infinite map, simulated.
+fn reachable_tiled(map: &Map<Node>, steps: usize) -> usize {
+    let tiled = map.tiled();
+    let reached = search::bfs(
+        [start(map)],
+        |v| {
+            tiled
+                .neighbours(*v)
+                .filter(|(_, _, node)| node.available)
+                .map(|(_, n, _)| n)
+        },
+        Some(steps),
+    );
+    reached
+        .distances
+        .values()
+        .filter(|d| *d % 2 == steps % 2)
+        .count()
 }
 
-#[test]
-fn part_2() {
-    let mut map = input(INPUT);
-    let mut queue = VecDeque::new();
-    let start = map
-        .nodes
+/// Plots reachable in exactly `steps` on the infinite map. `steps` must reach
+/// an edge of the map and then cross whole maps.
+///
+/// With the clear lanes through the start and along the edges of the real
+/// inputs the count grows as a quadratic in the number of whole maps crossed,
+/// so simulating the first three gives the rest.
+fn reachable_shortcut(map: &Map<Node>, steps: usize) -> usize {
+    let half = map.cols() / 2;
+    let n = (steps - half) / map.cols();
+    assert_eq!(n * map.cols() + half, steps, "steps don't end on an edge");
+
+    let [a, b, c] = [0, 1, 2].map(|i| reachable_tiled(map, half + i * map.cols()));
+    a + n * (b - a) + n * (n - 1) / 2 * (c + a - 2 * b)
+}
+
+/// Plots reachable in exactly `steps`, the ones first reached on the last
+/// step in yellow and the others in green.
+fn frontier(map: &Map<Node>, distances: &GridReached, steps: usize) -> Map<Pixel> {
+    let tiles = map
+        .nodes()
         .iter()
-        .enumerate()
-        .find_map(|(i, n)| n.candidate_for_generation.as_ref().map(|_| i))
-        .unwrap();
-    queue.push_back((0, map.converter().to_vector(start)));
-
-    let mut mem = HashMap::new();
-
-    while let Some((steps, i)) = queue.pop_front() {
-        for d in Direction::all() {
-            let d = d.to_vector();
-            let candidate = i + d;
-            let node = if let Some(c) = map.at_mut(candidate) {
-                c
-            } else {
-                continue;
-            };
-            if !node.available {
-                continue;
-            }
-
-            match mem.entry(candidate) {
-                std::collections::hash_map::Entry::Occupied(_) => {
-                    continue;
-                }
-                std::collections::hash_map::Entry::Vacant(v) => {
-                    let _ = v.insert(steps + 1);
-                    queue.push_back((steps + 1, candidate));
-                }
-            }
+        .zip(&distances.distances)
+        .map(|(node, d)| match d {
+            Some(d) if *d == steps => image::YELLOW,
+            Some(d) if *d < steps && d % 2 == steps % 2 => image::GREEN,
+            _ if node.available => image::BLACK,
+            _ => image::GREY,
+        })
+        .collect();
+    Map::new(tiles, map.cols())
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Map<Node>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Step Counter";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        self::input(Self::DAY, input)
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        reachable(input, 64)
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        reachable_shortcut(input, 26501365)
+    }
+
+    /// Plots reachable in exactly 64 steps in green, the ones reached last
+    /// in yellow.
+    fn render(input: &Self::Input) -> Option<Image> {
+        let distances = distances(input, Some(64));
+        Some(frontier(input, &distances, 64).to_bmp(|p| *p))
+    }
+
+    /// The plots reachable after each of the 64 steps.
+    fn animate(input: &Self::Input, recorder: &mut Recorder) {
+        let distances = distances(input, Some(64));
+        for steps in 0..=64 {
+            recorder.record(&frontier(input, &distances, steps), |p| *p);
         }
     }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day21::part_1(&Day21::parse(input)?))
+}
 
-    // Copied from
-    // https://github.com/villuna/aoc23/wiki/A-Geometric-solution-to-advent-of-code-2023,-day-21
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day21::part_2(&Day21::parse(input)?))
+}
 
-    let even_corners = mem.values().filter(|v| **v % 2 == 0 && **v > 65).count();
-    let odd_corners = mem.values().filter(|v| **v % 2 == 1 && **v > 65).count();
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::Day21;
+    use crate::Solution;
+
+    const EXAMPLE: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(21, |input| {
+            crate::answers::check(21, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(21, |input| {
+            crate::answers::check(21, 2, super::part_2(input).unwrap());
+        });
+    }
 
-    let even_full = mem.values().filter(|v| **v % 2 == 0).count();
-    let odd_full = mem.values().filter(|v| **v % 2 == 1).count();
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::reachable(&Day21::parse(EXAMPLE).unwrap(), 6), 16);
+    }
 
-    // This is 202300 but im writing it out here to show the process
-    let n = ((26501365 - (map.cols / 2)) / map.cols) as usize;
-    assert_eq!(n, 202300);
+    #[test]
+    fn example_tiled() {
+        let map = Day21::parse(EXAMPLE).unwrap();
+        assert_eq!(super::reachable_tiled(&map, 6), 16);
+        assert_eq!(super::reachable_tiled(&map, 10), 50);
+        assert_eq!(super::reachable_tiled(&map, 50), 1594);
+        assert_eq!(super::reachable_tiled(&map, 100), 6536);
+    }
 
-    let ans = ((n + 1) * (n + 1)) * odd_full + (n * n) * even_full - (n + 1) * odd_corners
-        + n * even_corners;
+    // The part 2 shortcut relies on the clear lanes around the start of the
+    // real input, the example doesn't have them. Check it against the
+    // simulation on the real input instead.
+    #[test]
+    fn shortcut() {
+        crate::input::with_input(21, |input| {
+            let map = Day21::parse(input).unwrap();
+            let steps = map.cols() / 2 + 4 * map.cols();
+            assert_eq!(
+                super::reachable_shortcut(&map, steps),
+                super::reachable_tiled(&map, steps)
+            );
+        });
+    }
 
-    println!("day 21 part 2 = {ans}");
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(2))]
+
+        // Generated maps have the same lanes, and one more map than the
+        // shortcut simulates checks the quadratic.
+        #[test]
+        fn shortcut_generated(seed: u64) {
+            let map = Day21::parse(&crate::generate::input(21, seed).unwrap()).unwrap();
+            let steps = map.cols() / 2 + 3 * map.cols();
+            prop_assert_eq!(
+                super::reachable_shortcut(&map, steps),
+                super::reachable_tiled(&map, steps)
+            );
+        }
+    }
 }
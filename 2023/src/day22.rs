@@ -1,17 +1,12 @@
-use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    ops::Range,
-    str::FromStr,
-};
+use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
-use range_ext::intersect::Intersect;
 
-const INPUT: &str = std::include_str!("input/day22.txt");
+use crate::{parse, search, IntervalSet, ParseError, Solution};
 
 type Vector = euclid::Vector3D<isize, ()>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Brick {
     a: Vector,
     b: Vector,
@@ -19,72 +14,82 @@ struct Brick {
 }
 
 impl Brick {
-    fn new(input: &str) -> Self {
-        fn parse_vec(input: &str) -> Vector {
-            let (a, r) = input.split_once(",").unwrap();
-            let (b, c) = r.split_once(",").unwrap();
-            let a = isize::from_str(a).unwrap();
-            let b = isize::from_str(b).unwrap();
-            let c = isize::from_str(c).unwrap();
-            Vector::new(a, b, c)
-        }
-        let (a, b) = input.split_once("~").unwrap();
-        let a = parse_vec(a);
-        let b = parse_vec(b);
-        Self {
+    fn new(l: parse::Line) -> Result<Self, ParseError> {
+        let parse_vec = |input| {
+            let (a, r) = l.split_once(input, ",")?;
+            let (b, c) = l.split_once(r, ",")?;
+            let v = Vector::new(l.parse(a)?, l.parse(b)?, l.parse(c)?);
+            if v.z < 1 {
+                return Err(l.error(c, "bricks can't go below z = 1"));
+            }
+            Ok(v)
+        };
+        let (a, b) = l.split_once(l.text, "~")?;
+        let a = parse_vec(a)?;
+        let b = parse_vec(b)?;
+        Ok(Self {
             a,
             b,
             below: Vec::new(),
-        }
+        })
     }
 
-    fn x_range(&self) -> Range<isize> {
+    fn x_range(&self) -> IntervalSet<isize> {
         let (a, b) = super::minmax(self.a.x, self.b.x);
-        a..(b + 1)
+        (a..(b + 1)).into()
     }
-    fn y_range(&self) -> Range<isize> {
+    fn y_range(&self) -> IntervalSet<isize> {
         let (a, b) = super::minmax(self.a.y, self.b.y);
-        a..(b + 1)
+        (a..(b + 1)).into()
     }
     fn zs(&self) -> (isize, isize) {
         super::minmax(self.a.z, self.b.z)
     }
 
     fn intersect(&self, other: &Self) -> bool {
-        self.x_range().does_intersect(&other.x_range())
-            && self.y_range().does_intersect(&other.y_range())
+        !self.x_range().is_disjoint(&other.x_range())
+            && !self.y_range().is_disjoint(&other.y_range())
     }
 }
 
-struct Game {
+#[derive(Clone)]
+pub struct Game {
     bricks: Vec<Brick>,
 }
 
 impl Game {
-    fn new(input: &str) -> Self {
-        let bricks = input.lines().map(Brick::new).collect();
+    fn new(day: u8, input: &str) -> Result<Self, ParseError> {
+        let lines = parse::lines(day, input).collect_vec();
+        let bricks = lines
+            .iter()
+            .copied()
+            .map(Brick::new)
+            .collect::<Result<_, _>>()?;
         let mut g = Self { bricks };
-        g.update_below();
-        g
+        g.update_below(&lines)?;
+        Ok(g)
     }
 
-    fn update_below(&mut self) {
+    /// Errors on the later of two bricks that share a cube.
+    fn update_below(&mut self, lines: &[parse::Line]) -> Result<(), ParseError> {
         for (a, b) in (0..self.bricks.len()).tuple_combinations() {
             if !self.bricks[a].intersect(&self.bricks[b]) {
                 continue;
             }
             let (a0, a1) = self.bricks[a].zs();
             let (b0, b1) = self.bricks[b].zs();
-            assert_ne!(a1, b0, "{:?}, {:?}", &self.bricks[a], &self.bricks[b]);
-            assert_ne!(b1, a0, "{:?}, {:?}", &self.bricks[a], &self.bricks[b]);
+            if a0 <= b1 && b0 <= a1 {
+                let l = lines[b];
+                return Err(l.error(l.text, format!("overlaps the brick on line {}", a + 1)));
+            }
             if a1 > b0 {
                 // A is above b.
                 self.bricks[a].below.push(b);
             } else {
-                assert!(b1 > a0, "{:?}, {:?}", &self.bricks[a], &self.bricks[b]);
                 self.bricks[b].below.push(a);
             }
         }
+        Ok(())
     }
 
     fn settle(&mut self) {
@@ -131,42 +136,117 @@ impl Game {
     }
 }
 
-#[test]
-fn part_1() {
-    let mut game = Game::new(INPUT);
-    game.settle();
-    let supporting = game.supporting();
-    let ans = supporting
-        .values()
-        .filter(|(v, _)| {
-            v.iter()
-                .all(|v| supporting.get(v).as_ref().unwrap().1.len() > 1)
-        })
-        .count();
-    println!("day 22 part 1 = {ans}");
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Game;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "Sand Slabs";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Game::new(Self::DAY, input)
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        let mut game = input.clone();
+        game.settle();
+        let supporting = game.supporting();
+        supporting
+            .values()
+            .filter(|(v, _)| {
+                v.iter()
+                    .all(|v| supporting.get(v).as_ref().unwrap().1.len() > 1)
+            })
+            .count()
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let mut game = input.clone();
+        game.settle();
+        let supporting = game.supporting();
+
+        (0..game.bricks.len())
+            .map(|i| {
+                // A brick falls once all the bricks it rests on have, it's
+                // seen again from its last supporter if it isn't yet.
+                let mut fallen = HashSet::new();
+                let falling = search::bfs(
+                    [i],
+                    |n| {
+                        fallen.insert(*n);
+                        supporting[n]
+                            .0
+                            .iter()
+                            .copied()
+                            .filter(|s| supporting[s].1.iter().all(|b| fallen.contains(b)))
+                            .collect::<Vec<_>>()
+                    },
+                    None,
+                );
+                falling.distances.len() - 1
+            })
+            .sum()
+    }
 }
 
-#[test]
-fn part_2() {
-    let mut game = Game::new(INPUT);
-    game.settle();
-    let supporting = game.supporting();
-
-    let ans: usize = (0..game.bricks.len())
-        .map(|i| {
-            let mut queue = VecDeque::new();
-            let mut falling = HashSet::new();
-            queue.push_back(i);
-            while let Some(n) = queue.pop_front() {
-                let (supporting, supported_by) = supporting.get(&n).unwrap();
-                if n == i || supported_by.iter().all(|s| falling.contains(s)) {
-                    if falling.insert(n) {
-                        queue.extend(supporting.iter().copied());
-                    }
-                }
-            }
-            falling.len() - 1
-        })
-        .sum();
-    println!("day 22 part 1 = {ans}");
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day22::part_1(&Day22::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day22::part_2(&Day22::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(22, |input| {
+            crate::answers::check(22, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(22, |input| {
+            crate::answers::check(22, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 5);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 7);
+    }
+
+    #[test]
+    fn overlapping_bricks() {
+        let e = super::part_1("1,1,1~1,1,3\n1,1,3~1,1,4\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+        assert_eq!(e.message, "overlaps the brick on line 1");
+    }
+
+    #[test]
+    fn brick_on_the_ground() {
+        let e = super::part_1("1,0,1~1,2,1\n1,0,0~1,2,0\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 5));
+        assert_eq!(e.message, "bricks can't go below z = 1");
+    }
 }
@@ -1,40 +1,24 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use super::{Direction, Map};
+use super::{Direction, Map, ParseError, Solution};
 
-const INPUT: &str = std::include_str!("input/day23.txt");
-
-enum Tile {
+pub enum Tile {
     Ground,
     Rock,
     Slope(Direction),
 }
 
 impl Tile {
-    fn new(c: char) -> Self {
-        match c {
+    fn new(c: char) -> Option<Self> {
+        Some(match c {
             '.' => Self::Ground,
             '#' => Self::Rock,
-            '>' => Self::Slope(Direction::Right),
-            '^' => Self::Slope(Direction::Up),
-            '<' => Self::Slope(Direction::Left),
-            'v' => Self::Slope(Direction::Down),
-            o => panic!("unknown tile {o}"),
-        }
+            '>' | '^' | '<' | 'v' => Self::Slope(Direction::try_from(c).ok()?),
+            _ => return None,
+        })
     }
 }
 
-fn input(input: &str) -> Map<Tile> {
-    let cols = input.lines().next().unwrap().len();
-    let tiles = input
-        .lines()
-        .map(|l| l.chars())
-        .flatten()
-        .map(Tile::new)
-        .collect();
-    Map::new(tiles, cols)
-}
-
 fn walk(pos: usize, map: &Map<Tile>, slopes: bool) -> usize {
     let mut stack = VecDeque::new();
     stack.push_back((HashSet::new(), pos));
@@ -58,19 +42,12 @@ fn walk(pos: usize, map: &Map<Tile>, slopes: bool) -> usize {
             continue;
         }
 
-        for d in dirs {
-            let n = v + d.to_vector();
-            let ni = if let Some(i) = map.converter().to_idx(n) {
-                i
-            } else {
-                continue;
-            };
+        for (_, ni) in map.converter().neighbours(v, dirs) {
             if visited.contains(&ni) {
                 continue;
             }
-            match map.at(ni).unwrap() {
-                Tile::Rock => continue,
-                _ => (),
+            if let Tile::Rock = map.at(ni).unwrap() {
+                continue;
             }
             stack.push_back((visited.clone(), ni));
         }
@@ -91,22 +68,14 @@ fn visit_and_acc(
     }
 
     let mut next = None;
-    for d in Direction::all() {
-        if inclusive {
-            if d != direction {
-                continue;
-            }
-        } else {
-            if d == direction {
-                continue;
-            }
+    for (d, idx, tile) in map.neighbours(pos) {
+        if (d == direction) != inclusive {
+            continue;
         }
-        let v = d.to_vector() + v;
-        let idx = map.converter().to_idx(v);
-        match idx.and_then(|v| map.at(v)) {
-            None | Some(Tile::Rock) => continue,
-            Some(Tile::Ground) | Some(Tile::Slope(_)) => {
-                if let Some(_) = std::mem::replace(&mut next, Some((idx.unwrap(), d.opposite()))) {
+        match tile {
+            Tile::Rock => continue,
+            Tile::Ground | Tile::Slope(_) => {
+                if next.replace((idx, d.opposite())).is_some() {
                     return Some((pos, count));
                 }
             }
@@ -151,51 +120,124 @@ fn walk_compressed(start: usize, end: usize, map: &HashMap<usize, Vec<(usize, us
     }
     max
 }
-#[test]
-fn part_1() {
-    let map = input(INPUT);
-    let start = map
-        .nodes
-        .iter()
-        .enumerate()
-        .find_map(|(i, n)| match n {
-            Tile::Ground => Some(i),
-            _ => None,
-        })
-        .unwrap();
-    let ans = walk(start, &map, true) - 1;
-    println!("day 23 part 1 = {ans}");
-}
 
-// This is rather slow, couldn't figure out any memoization to improve, but
-// compressing the map makes it run to completion in < 1min.
-#[test]
-fn part_2() {
-    let map = input(INPUT);
-    let start = map
-        .nodes
-        .iter()
-        .enumerate()
-        .find_map(|(i, n)| match n {
-            Tile::Ground => Some(i),
-            _ => None,
-        })
-        .unwrap();
-    let end = map
-        .nodes
-        .iter()
-        .enumerate()
-        .find_map(|(i, n)| {
-            if map.converter().to_vector(i).y != map.bounds().y - 1 {
-                return None;
-            }
-            match n {
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input = Map<Tile>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "A Long Walk";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Map::parse(Self::DAY, input, Tile::new)
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        let map = input;
+        let start = map
+            .nodes
+            .iter()
+            .enumerate()
+            .find_map(|(i, n)| match n {
                 Tile::Ground => Some(i),
                 _ => None,
-            }
-        })
-        .unwrap();
-    let compressed = compress(&map);
-    let ans = walk_compressed(start, end, &compressed);
-    println!("day 23 part 2 = {ans}");
+            })
+            .unwrap();
+        walk(start, map, true) - 1
+    }
+
+    // This is rather slow, couldn't figure out any memoization to improve, but
+    // compressing the map makes it run to completion in < 1min.
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let map = input;
+        let start = map
+            .nodes
+            .iter()
+            .enumerate()
+            .find_map(|(i, n)| match n {
+                Tile::Ground => Some(i),
+                _ => None,
+            })
+            .unwrap();
+        let end = map
+            .nodes
+            .iter()
+            .enumerate()
+            .find_map(|(i, n)| {
+                if map.converter().to_vector(i).y != map.bounds().y - 1 {
+                    return None;
+                }
+                match n {
+                    Tile::Ground => Some(i),
+                    _ => None,
+                }
+            })
+            .unwrap();
+        let compressed = compress(map);
+        walk_compressed(start, end, &compressed)
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day23::part_1(&Day23::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day23::part_2(&Day23::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(23, |input| {
+            crate::answers::check(23, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(23, |input| {
+            crate::answers::check(23, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 94);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 154);
+    }
 }
@@ -1,11 +1,12 @@
-use std::str::FromStr;
-
 use itertools::Itertools;
-const INPUT: &str = std::include_str!("input/day24.txt");
 
-type Vector = euclid::Vector3D<f64, ()>;
+use crate::{parse, ParseError, Solution};
+
+type Vector = euclid::Vector3D<i64, ()>;
+type IVector = euclid::Vector3D<i128, ()>;
 
-struct Hail {
+#[derive(Clone)]
+pub struct Hail {
     pos: Vector,
     speed: Vector,
 }
@@ -19,137 +20,210 @@ impl Hail {
         // Ts*Vsy = (Voy*To - Py)
         // Ts*Vsy = Voy * (Px + Vsx * Ts) / Vox - Py
         // Ts* (Vsy - Voy * Vsx / Vox) =  Voy * Px / Vox - Py
-        let p = self.pos - other.pos;
-        let ts = (other.speed.y * p.x / other.speed.x - p.y)
-            / (self.speed.y - other.speed.y * self.speed.x / other.speed.x);
+        let (ps, vs) = (self.pos.to_f64(), self.speed.to_f64());
+        let (po, vo) = (other.pos.to_f64(), other.speed.to_f64());
+        let p = ps - po;
+        let ts = (vo.y * p.x / vo.x - p.y) / (vs.y - vo.y * vs.x / vo.x);
         if ts < 0.0 {
             return None;
         }
-        let to = (p.x + self.speed.x * ts) / other.speed.x;
+        let to = (p.x + vs.x * ts) / vo.x;
         if to < 0.0 {
             return None;
         }
-        let end = self.pos + self.speed * ts;
+        let end = ps + vs * ts;
         Some(end.xy())
     }
-}
 
-fn input(input: &str) -> impl Iterator<Item = Hail> + '_ {
-    fn parse_vec(input: &str) -> Vector {
-        let (x, r) = input.split_once(",").unwrap();
-        let (y, z) = r.split_once(",").unwrap();
-        let x = x.trim();
-        let y = y.trim();
-        let z = z.trim();
-        Vector::new(
-            f64::from_str(x).unwrap(),
-            f64::from_str(y).unwrap(),
-            f64::from_str(z).unwrap(),
+    /// Position and speed as seen from a rock flying at `vx`, `vy` in x and y.
+    fn seen_from(&self, vx: i128, vy: i128) -> (IVector, IVector) {
+        let speed = self.speed.cast::<i128>();
+        (
+            self.pos.cast(),
+            IVector::new(speed.x - vx, speed.y - vy, speed.z),
         )
     }
-    input.lines().map(|l| {
-        let (p, s) = l.split_once(" @ ").unwrap();
-        Hail {
-            pos: parse_vec(p),
-            speed: parse_vec(s),
-        }
-    })
 }
 
-#[test]
-fn part_1() {
-    let lo = 200000000000000.0;
-    let hi = 400000000000000.0;
-    let hail = input(INPUT)
+fn input(day: u8, input: &str) -> Result<Vec<Hail>, ParseError> {
+    parse::lines(day, input)
+        .map(|l| {
+            let parse_vec = |input| {
+                let (x, r) = l.split_once(input, ",")?;
+                let (y, z) = l.split_once(r, ",")?;
+                Ok(Vector::new(
+                    l.parse(x.trim())?,
+                    l.parse(y.trim())?,
+                    l.parse(z.trim())?,
+                ))
+            };
+            let (p, s) = l.split_once(l.text, " @ ")?;
+            Ok(Hail {
+                pos: parse_vec(p)?,
+                speed: parse_vec(s)?,
+            })
+        })
+        .collect()
+}
+
+/// Counts the crossing paths inside the `lo..=hi` test area, ignoring z.
+fn intersections(hail: &[Hail], lo: f64, hi: f64) -> usize {
+    let hail = hail
+        .iter()
+        .cloned()
         .map(|mut hail| {
-            hail.pos.z = 0.0;
-            hail.speed.z = 0.0;
+            hail.pos.z = 0;
+            hail.speed.z = 0;
             hail
         })
         .collect::<Vec<_>>();
-    let ans = hail
-        .iter()
+    hail.iter()
         .tuple_combinations()
         .filter(|(a, b)| {
             a.intersect2d(b)
                 .map(|p| p.x >= lo && p.y >= lo && p.x <= hi && p.y <= hi)
                 .unwrap_or(false)
         })
-        .count();
-    println!("day 24 part 1 = {ans}");
+        .count()
 }
 
-#[test]
-fn part_2() {
-    // Solve with equations.
-    // 3 hailstones are selected, each one is Vi.
-    // Vs * Ti + Ps - Vi * Ti - Pi = 0
-    // We have 9 variables, Ps..., Vs... T1, T2, T3.
-    type SVector = nalgebra::base::SVector<f64, 9>;
-    type Matrix = nalgebra::base::SMatrix<f64, 9, 9>;
-
-    let hail = input(INPUT).collect::<Vec<_>>();
-
-    // Pick some different hail that makes the calculated error lower.
-    let hail_delta = 3;
-    let f = |v: SVector| {
-        let mut o = SVector::default();
-        for i in 0..3 {
-            let ps = v.index(i);
-            let vs = v.index(3 + i);
-            for j in 0..3 {
-                let ti = v.index(6 + j);
-                let h = &hail[j + hail_delta];
-                let pi = h.pos.to_array()[i];
-                let vi = h.speed.to_array()[i];
-                *o.index_mut(3 * j + i) = vs * ti + ps - vi * ti - pi
-            }
-        }
-        o
-    };
-
-    // Jacobian of f.
-    //
-    // [1] [Ti] [vs - vi]
-    let j = |v: SVector| {
-        let mut o = Matrix::default();
-        for i in 0..3 {
-            let vs = v.index(3 + i);
-            for j in 0..3 {
-                let ti = v.index(6 + j);
-                let h = &hail[j + hail_delta];
-                let vi = h.speed.to_array()[i];
-
-                let row = 3 * j + i;
-                *o.index_mut((row, i)) = 1.;
-                *o.index_mut((row, i + 3)) = *ti;
-                *o.index_mut((row, 6 + j)) = vs - vi;
-                // *o.index_mut((i, row)) = 1.;
-                // *o.index_mut((i + 3, row)) = *ti;
-                // *o.index_mut((6 + j, row)) = vs - vi;
-            }
-        }
-        o
-    };
-    let solution = eqsolver::multivariable::MultiVarNewton::new(f, j)
-        .with_tol(1e-9)
-        .solve(SVector::from_vec(vec![
-            20., -20., 20., 5., -2., 20., 10., 20., 30.,
-        ]));
-
-    println!("solution = {solution:?}");
-    let solution = solution.expect("bad solution");
-    let p = Vector::new(*solution.index(0), *solution.index(1), *solution.index(2));
-    let v = Vector::new(*solution.index(3), *solution.index(4), *solution.index(5));
-    let t = [*solution.index(6), *solution.index(7), *solution.index(8)];
-    for i in 0..3 {
-        let hail = &hail[hail_delta + i];
-        let p = p + v * t[i] - (hail.pos + hail.speed * t[i]);
-        // Print to choose hail delta we want error to be 0 here.
-        // An alternative would be to use combinations of hail instead and pick
-        // the one with the smallest error. But, hey, this works.
-        println!("{i} => {}", p.length());
+/// Sum of the coordinates the rock is thrown from if it flies at `vx`, `vy`
+/// in x and y. Seen from the rock every hail flies through that point at the
+/// time it gets hit, so two crossing paths give the point and every hail has
+/// to reach it at a whole time.
+fn throw_at(hail: &[Hail], vx: i128, vy: i128) -> Option<i128> {
+    let seen = |h: &Hail| h.seen_from(vx, vy);
+    let (pa, va) = seen(&hail[0]);
+    // Time along the first path to where another one crosses it, times det.
+    let (t, det) = hail[1..].iter().map(seen).find_map(|(pb, vb)| {
+        let d = pb - pa;
+        let det = va.x * vb.y - va.y * vb.x;
+        (det != 0).then_some((d.x * vb.y - d.y * vb.x, det))
+    })?;
+    if t % det != 0 {
+        return None;
+    }
+    let start = pa + va * (t / det);
+    let times = hail
+        .iter()
+        .map(|h| {
+            let (p, v) = seen(h);
+            let t = match (v.x, v.y) {
+                (0, 0) => return None,
+                (0, y) => (start.y - p.y) / y,
+                (x, _) => (start.x - p.x) / x,
+            };
+            let at = p + v * t;
+            (t >= 0 && at.x == start.x && at.y == start.y).then_some(t)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    // Two hits at different times give the rock's z and speed in z.
+    let z = |i: usize| hail[i].pos.z as i128 + hail[i].speed.z as i128 * times[i];
+    let (a, b) = (0..hail.len())
+        .tuple_combinations()
+        .find(|(a, b)| times[*a] != times[*b])?;
+    let (dz, dt) = (z(b) - z(a), times[b] - times[a]);
+    if dz % dt != 0 {
+        return None;
+    }
+    let vz = dz / dt;
+    let z0 = z(a) - vz * times[a];
+    (0..hail.len())
+        .all(|i| z(i) == z0 + vz * times[i])
+        .then_some(start.x + start.y + z0)
+}
+
+/// Tries rock speeds in x and y further and further from standing still
+/// until one hits every hail.
+fn rock_throw(hail: &[Hail]) -> Option<i128> {
+    let range = 1000;
+    (0..=range)
+        .flat_map(|r| (-r..=r).flat_map(move |i| [(i, -r), (i, r), (-r, i), (r, i)]))
+        .find_map(|(vx, vy)| throw_at(hail, vx, vy))
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    type Input = Vec<Hail>;
+    type Part1 = usize;
+    type Part2 = i64;
+
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "Never Tell Me The Odds";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        self::input(Self::DAY, input)
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        intersections(input, 200000000000000.0, 400000000000000.0)
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        rock_throw(input).expect("no rock hits every hail") as i64
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day24::part_1(&Day24::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<i64, ParseError> {
+    Ok(Day24::part_2(&Day24::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Day24;
+    use crate::Solution;
+
+    const EXAMPLE: &str = "\
+19, 13, 30 @ -2,  1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @  1, -5, -3
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(24, |input| {
+            crate::answers::check(24, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(24, |input| {
+            crate::answers::check(24, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(
+            super::intersections(&Day24::parse(EXAMPLE).unwrap(), 7.0, 27.0),
+            2
+        );
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 47);
+    }
+
+    #[test]
+    fn real_scale_part_2() {
+        let input = "\
+311468073469924, 239026714547185, 232994428654441 @ -123, -82, 125
+377077404491653, 229919228075418, 202726113095661 @ -123, 67, -1
+262414640294297, 321527647725218, 156428032353077 @ 179, -193, 143
+235782324349103, 382250926467328, 237029463087069 @ 197, -168, -119
+241105477002977, 215244474248983, 274180941114129 @ 188, 102, -179
+416501359946825, 136572627079038, 281148457112097 @ -46, 199, -185
+";
+        assert_eq!(super::part_2(input).unwrap(), 768476045499460);
     }
-    let ans = p.x + p.y + p.z;
-    println!("day 24 part 2 = {ans}");
 }
@@ -1,101 +1,93 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-const INPUT: &str = std::include_str!("input/day25.txt");
+use crate::{parse, search, ParseError, Solution};
 
-fn input(input: &str) -> (HashMap<usize, Vec<usize>>, HashMap<String, usize>) {
-    input.lines().fold(
-        (
-            HashMap::<usize, Vec<usize>>::new(),
-            HashMap::<String, usize>::new(),
-        ),
+type Names = HashMap<String, usize>;
+
+fn input(day: u8, input: &str) -> Result<(HashMap<usize, Vec<usize>>, Names), ParseError> {
+    parse::lines(day, input).try_fold(
+        (HashMap::<usize, Vec<usize>>::new(), Names::new()),
         |(mut map, mut names), l| {
-            let (k, r) = l.split_once(":").unwrap();
+            let name = |n: &str| {
+                if n.is_empty() || !n.chars().all(|c| c.is_ascii_lowercase()) {
+                    return Err(l.error(n, "expected component name"));
+                }
+                Ok(n.to_string())
+            };
+            let (k, r) = l.split_once(l.text, ": ")?;
             let namelen = names.len();
-            let ki = *names.entry(k.to_string()).or_insert(namelen);
-            for i in r.trim().split(" ") {
-                assert_ne!(i, "");
-                let i = i.trim();
+            let ki = *names.entry(name(k)?).or_insert(namelen);
+            for i in r.split(" ") {
                 let namelen = names.len();
-                let ii = *names.entry(i.to_string()).or_insert(namelen);
+                let ii = *names.entry(name(i)?).or_insert(namelen);
                 map.entry(ki).or_default().push(ii);
                 map.entry(ii).or_default().push(ki);
             }
-            (map, names)
+            Ok((map, names))
         },
     )
 }
 
-struct Graph {
+pub struct Graph {
     nodes: HashMap<usize, Vec<usize>>,
     edges: HashSet<(usize, usize)>,
 }
 
 impl Graph {
-    fn new(i: &str) -> Self {
-        let (nodes, _) = input(i);
+    fn new(day: u8, i: &str) -> Result<Self, ParseError> {
+        let (nodes, _) = input(day, i)?;
 
         let edges = nodes
             .iter()
-            .map(|(a, v)| v.iter().map(|b| super::minmax(*a, *b)))
-            .flatten()
+            .flat_map(|(a, v)| v.iter().map(|b| super::minmax(*a, *b)))
             .collect::<HashSet<_>>();
-        Self { nodes, edges }
+        Ok(Self { nodes, edges })
     }
 
-    fn reach_node(
-        &self,
-        start: usize,
-        end: usize,
-        used_edges: &mut HashSet<(usize, usize)>,
-        visited: &mut HashSet<usize>,
-    ) -> bool {
-        if !visited.insert(start) {
+    /// Adds one more path from `start` to `end` that shares no edge with
+    /// the ones in `flow` and doesn't use the edge between them, rerouting
+    /// earlier paths where needed. A path taken greedily can block the
+    /// others on small graphs.
+    fn augment(&self, start: usize, end: usize, flow: &mut HashSet<(usize, usize)>) -> bool {
+        let reached = search::bfs(
+            [start],
+            |&i| {
+                let flow = &*flow;
+                self.nodes[&i].iter().copied().filter(move |e| {
+                    super::minmax(i, *e) != super::minmax(start, end) && !flow.contains(&(i, *e))
+                })
+            },
+            None,
+        );
+        let Some(path) = reached.path(&end) else {
             return false;
-        }
-        let next = self.nodes.get(&start).unwrap();
-        for e in next {
-            if visited.contains(e) {
-                continue;
-            }
-            let edge = super::minmax(start, *e);
-            if !used_edges.insert(edge) {
-                continue;
-            }
-            if *e == end {
-                return true;
-            }
-            if self.reach_node(*e, end, used_edges, visited) {
-                return true;
+        };
+        for (a, b) in path.into_iter().tuple_windows() {
+            if !flow.remove(&(b, a)) {
+                flow.insert((a, b));
             }
-
-            used_edges.remove(&edge);
         }
-
-        return false;
+        true
     }
 
     fn fill_group(&self, rem: &mut HashSet<usize>, avoid: &HashSet<(usize, usize)>) -> usize {
-        let mut count = 0;
-        let mut queue = VecDeque::new();
         let item = *rem.iter().next().unwrap();
-        queue.push_back(item);
-        while let Some(i) = queue.pop_front() {
-            if !rem.remove(&i) {
-                continue;
-            }
-            count += 1;
-            for e in self.nodes.get(&i).unwrap().iter() {
-                let edge = super::minmax(i, *e);
-                if avoid.contains(&edge) {
-                    continue;
-                }
-                queue.push_back(*e);
-            }
+        let group = search::bfs(
+            [item],
+            |&i| {
+                self.nodes[&i]
+                    .iter()
+                    .copied()
+                    .filter(move |e| !avoid.contains(&super::minmax(i, *e)))
+            },
+            None,
+        );
+        for i in group.distances.keys() {
+            rem.remove(i);
         }
-
-        count
+        group.distances.len()
     }
 
     fn try_bisect(&self, avoid: &HashSet<(usize, usize)>) -> Option<usize> {
@@ -119,30 +111,24 @@ impl Graph {
 
 /// This is horribly slow, but we basically try to reduce the number of edge combinations before we
 /// check for the bisection. Takes about 2min to run.
-#[test]
-fn part_1() {
-    let g = Graph::new(INPUT);
-
+fn bisections(g: &Graph) -> Vec<usize> {
     let mut candidates = HashMap::new();
     for (start, end) in g.edges.iter() {
-        let mut used = HashSet::new();
-        used.insert((*start, *end));
+        let mut flow = HashSet::new();
         let mut i = 0;
-        let e = loop {
-            if g.reach_node(*start, *end, &mut used, &mut HashSet::new()) {
-                if i >= 3 {
-                    break None;
-                }
-                i += 1;
-            } else {
-                break (i > 0 && i < 3).then_some((*start, *end));
-            }
-        };
-        if let Some(e) = e {
-            candidates.insert(e, used);
+        while i < 3 && g.augment(*start, *end, &mut flow) {
+            i += 1;
+        }
+        if i > 0 && i < 3 {
+            let used = flow
+                .iter()
+                .map(|(a, b)| super::minmax(*a, *b))
+                .chain([(*start, *end)])
+                .collect::<HashSet<_>>();
+            candidates.insert((*start, *end), used);
         }
     }
-    let ans = candidates
+    candidates
         .keys()
         .copied()
         .tuple_combinations()
@@ -156,7 +142,91 @@ fn part_1() {
                 .then_some(ks)
         })
         .filter_map(|ks| g.try_bisect(&ks.into_iter().collect()))
-        .collect::<Vec<_>>();
+        .collect::<Vec<_>>()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    type Input = Graph;
+    type Part1 = usize;
+    type Part2 = std::convert::Infallible;
+
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "Snowverload";
+    const HAS_PART_2: bool = false;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Graph::new(Self::DAY, input)
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        bisections(input)
+            .into_iter()
+            .unique()
+            .exactly_one()
+            .expect("single bisection")
+    }
 
-    println!("day 25 part 1 = {ans:?} {}", ans.len());
+    fn part_2(_: &Self::Input) -> Self::Part2 {
+        unreachable!("day 25 has no part 2")
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day25::part_1(&Day25::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(25, |input| {
+            crate::answers::check(25, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 54);
+    }
+
+    #[test]
+    fn blocking_path() {
+        // The first path from sa to ta found depth first crosses every edge
+        // between the x and y cliques, leaving none for the path over yb-tc.
+        let input = "\
+sa: xa ta
+xa: yc xb xc xd
+yc: yd ye ya yb
+yd: xb ye ya yb
+xb: xc xd sa
+xc: ye xd sa
+ye: ya yb
+ya: tb yb
+tb: ta tc td te
+sa: xd
+yb: tc
+ta: tc td te
+tc: td te
+td: te
+";
+        assert_eq!(super::part_1(input).unwrap(), 50);
+    }
 }
@@ -1,129 +1,126 @@
 use regex::Regex;
-use std::{collections::HashMap, str::FromStr};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
 
-const INPUT: &str = std::include_str!("input/day3.txt");
-
-#[test]
-fn part_1() {
-    let matrix = INPUT.lines().collect::<Vec<_>>();
-
-    let is_symbol = |row: usize, col: usize| {
-        matrix
-            .get(row)
-            .and_then(|s| s.as_bytes().get(col).map(|c: &u8| *c as char))
-            .map(|c| c != '.' && !c.is_ascii_digit())
-            .unwrap_or(false)
-    };
+use crate::{Map, ParseError, Solution};
 
+/// Numbers in the schematic with the indices of their digits.
+fn numbers(map: &Map<char>) -> Vec<(usize, Range<usize>)> {
     let regex = Regex::new("[0-9]+").unwrap();
-
-    let ans: usize = matrix
-        .iter()
+    map.rows()
         .enumerate()
-        .map(|(row, line)| {
+        .flat_map(|(row, line)| {
+            let line = line.iter().collect::<String>();
+            let start = row * map.cols();
             regex
-                .find_iter(line)
+                .find_iter(&line)
                 .map(|m| {
-                    let v = usize::from_str(m.as_str()).unwrap();
-                    let mut range = m.range();
-                    if range.start > 0 {
-                        range.start -= 1;
-                        if is_symbol(row, range.start) {
-                            return v;
-                        }
-                    }
-                    if is_symbol(row, range.end) {
-                        return v;
-                    }
-                    range.end += 1;
-
-                    if let Some(r) = row.checked_sub(1) {
-                        if range.clone().any(|i| is_symbol(r, i)) {
-                            return v;
-                        }
-                    }
-                    let next_row = row + 1;
-                    if next_row < matrix.len() {
-                        if range.clone().any(|i| is_symbol(next_row, i)) {
-                            return v;
-                        }
-                    }
-                    0
+                    let v = m.as_str().parse().unwrap();
+                    (v, start + m.start()..start + m.end())
                 })
-                .sum::<usize>()
+                .collect::<Vec<_>>()
         })
-        .sum();
-
-    println!("day 3 part 1 = {ans}");
+        .collect()
 }
 
-#[test]
-fn part_2() {
-    let matrix = INPUT.lines().collect::<Vec<_>>();
+pub struct Day3;
 
-    let is_gear = |row: usize, col: usize| {
-        matrix
-            .get(row)
-            .and_then(|s| s.as_bytes().get(col).map(|c: &u8| *c as char))
-            .map(|c| c == '*')
-            .unwrap_or(false)
-    };
+impl Solution for Day3 {
+    type Input = Map<char>;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    let regex = Regex::new("[0-9]+").unwrap();
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Gear Ratios";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Map::parse(Self::DAY, input, |c| c.is_ascii_graphic().then_some(c))
+    }
 
-    let mut gears = HashMap::new();
-    let mut ans = 0;
-    for (row, line) in matrix.iter().enumerate() {
-        let look = regex
-            .find_iter(line)
-            .map(|m| {
-                let v = usize::from_str(m.as_str()).unwrap();
-                let mut range = m.range();
-                range.start = range.start.saturating_sub(1);
-                range.end += 1;
-
-                let prev = if let Some(r) = row.checked_sub(1) {
-                    itertools::Either::Left(range.clone().map(move |i| (r, i)))
-                } else {
-                    itertools::Either::Right(std::iter::empty())
-                };
-                let next_row = row + 1;
-                let nxt = if next_row < matrix.len() {
-                    itertools::Either::Left(range.clone().map(move |i| (next_row, i)))
-                } else {
-                    itertools::Either::Right(std::iter::empty())
-                };
-                prev.chain(nxt)
-                    .chain(std::iter::once((row, range.start)))
-                    .chain(std::iter::once((row, range.end - 1)))
-                    .map(move |(r, i)| (v, r, i))
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        let map = input;
+        let is_symbol = |c: &char| *c != '.' && !c.is_ascii_digit();
+
+        numbers(map)
+            .into_iter()
+            .filter(|(_, digits)| {
+                digits
+                    .clone()
+                    .any(|i| map.all_neighbours(i).any(|(_, _, c)| is_symbol(c)))
             })
-            .flatten();
-        for (value, r, i) in look {
-            if !is_gear(r, i) {
-                continue;
-            }
-            match gears.entry((r, i)) {
-                std::collections::hash_map::Entry::Occupied(mut o) => {
-                    let (count, val) = *o.get();
-                    let val = if count == 1 {
-                        let val = val * value;
-                        ans += val;
-                        val
-                    } else if count == 2 {
-                        ans -= val;
-                        0
-                    } else {
-                        0
-                    };
-                    *o.get_mut() = (count + 1, val);
-                }
-                std::collections::hash_map::Entry::Vacant(v) => {
-                    v.insert((1, value));
-                }
+            .map(|(v, _)| v)
+            .sum()
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let map = input;
+
+        let mut gears = HashMap::<usize, Vec<usize>>::new();
+        for (v, digits) in numbers(map) {
+            let adjacent = digits
+                .flat_map(|i| map.all_neighbours(i))
+                .filter(|(_, _, c)| **c == '*')
+                .map(|(_, i, _)| i)
+                .collect::<HashSet<_>>();
+            for gear in adjacent {
+                gears.entry(gear).or_default().push(v);
             }
         }
+
+        gears
+            .values()
+            .filter(|values| values.len() == 2)
+            .map(|values| values[0] * values[1])
+            .sum()
     }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day3::part_1(&Day3::parse(input)?))
+}
 
-    println!("day 3 part 2 = {ans}");
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day3::part_2(&Day3::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(3, |input| {
+            crate::answers::check(3, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(3, |input| {
+            crate::answers::check(3, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 4361);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 467835);
+    }
 }
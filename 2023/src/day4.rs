@@ -1,67 +1,124 @@
 use regex::Regex;
-use std::{
-    collections::{HashSet, VecDeque},
-    str::FromStr,
-};
-
-const INPUT: &str = std::include_str!("input/day4.txt");
-
-#[test]
-fn part_1() {
-    let lines = INPUT.lines();
-    let line_regex = Regex::new(r"Card +[0-9]+: ([0-9 ]+) \| ([0-9 ]+)").unwrap();
-    let number_regex = Regex::new("[0-9]+").unwrap();
-    let ans = lines
-        .map(|l| {
-            let capture = line_regex.captures(l).expect("captures");
-            let winning = number_regex.find_iter(capture.get(2).unwrap().as_str());
-            let winning = winning
-                .map(|m| usize::from_str(m.as_str()).unwrap())
-                .collect::<HashSet<_>>();
-
-            let count = number_regex
-                .find_iter(capture.get(1).unwrap().as_str())
-                .filter_map(|m| {
-                    let num = usize::from_str(m.as_str()).unwrap();
-                    winning.contains(&num).then_some(())
-                })
-                .count();
-            count.checked_sub(1).map(|c| 1 << c).unwrap_or(0)
-        })
-        .sum::<usize>();
-    println!("day 4 part 1 = {ans}");
+use std::collections::{HashSet, VecDeque};
+
+use crate::{parse, ParseError, Solution};
+
+pub struct Card {
+    id: usize,
+    numbers: Vec<usize>,
+    winning: HashSet<usize>,
 }
 
-#[test]
-fn part_2() {
-    let lines = INPUT.lines();
-    let line_regex = Regex::new(r"Card +([0-9]+): ([0-9 ]+) \| ([0-9 ]+)").unwrap();
-    let number_regex = Regex::new("[0-9]+").unwrap();
-    let (_, ans) = lines.fold((VecDeque::new(), 0usize), |(mut mem, score), l| {
-        let capture = line_regex.captures(l).expect("captures");
-        let winning = number_regex.find_iter(capture.get(3).unwrap().as_str());
-        let winning = winning
-            .map(|m| usize::from_str(m.as_str()).unwrap())
-            .collect::<HashSet<_>>();
-
-        let count = number_regex
-            .find_iter(capture.get(2).unwrap().as_str())
-            .filter_map(|m| {
-                let num = usize::from_str(m.as_str()).unwrap();
-                winning.contains(&num).then_some(())
+impl Card {
+    fn matches(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|num| self.winning.contains(num))
+            .count()
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Card>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Scratchcards";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let line_regex = Regex::new(r"Card +([0-9]+): ([0-9 ]+) \| ([0-9 ]+)").unwrap();
+        let number_regex = Regex::new("[0-9]+").unwrap();
+        parse::lines(Self::DAY, input)
+            .map(|l| {
+                let capture = l.captures(&line_regex)?;
+                let id = l.parse(capture.get(1).unwrap().as_str())?;
+                let numbers = number_regex
+                    .find_iter(capture.get(2).unwrap().as_str())
+                    .map(|m| l.parse(m.as_str()))
+                    .collect::<Result<_, _>>()?;
+                let winning = number_regex
+                    .find_iter(capture.get(3).unwrap().as_str())
+                    .map(|m| l.parse(m.as_str()))
+                    .collect::<Result<_, _>>()?;
+                Ok(Card {
+                    id,
+                    numbers,
+                    winning,
+                })
             })
-            .count();
-
-        let this_score = 1 + mem.pop_front().unwrap_or(0);
-        for i in 0..count {
-            if let Some(x) = mem.get_mut(i) {
-                *x += this_score;
-            } else {
-                mem.push_back(this_score);
-            }
-        }
-        println!("{l} => {count} {this_score} {mem:?}");
-        (mem, score + this_score)
-    });
-    println!("day 4 part 2 = {ans}");
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        input
+            .iter()
+            .map(|card| card.matches().checked_sub(1).map(|c| 1 << c).unwrap_or(0))
+            .sum()
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let (_, ans) = input
+            .iter()
+            .fold((VecDeque::new(), 0usize), |(mut mem, score), card| {
+                let count = card.matches();
+                let this_score = 1 + mem.pop_front().unwrap_or(0);
+                for i in 0..count {
+                    if let Some(x) = mem.get_mut(i) {
+                        *x += this_score;
+                    } else {
+                        mem.push_back(this_score);
+                    }
+                }
+                log::trace!("card {} => {count} {this_score} {mem:?}", card.id);
+                (mem, score + this_score)
+            });
+        ans
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day4::part_1(&Day4::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day4::part_2(&Day4::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(4, |input| {
+            crate::answers::check(4, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(4, |input| {
+            crate::answers::check(4, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 13);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 30);
+    }
 }
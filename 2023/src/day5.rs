@@ -1,145 +1,190 @@
 use itertools::Itertools;
 use regex::Regex;
-use std::{cmp, str::FromStr};
-
-const INPUT: &str = std::include_str!("input/day5.txt");
-
-#[test]
-fn part_1() {
-    let mut lines = INPUT.lines();
-    let numbers_regex = Regex::new("[0-9]+").unwrap();
-    let seeds = lines
-        .by_ref()
-        .find_map(|l| {
-            if !l.starts_with("seeds:") {
-                return None;
-            }
-            Some(
-                numbers_regex
-                    .find_iter(l)
-                    .map(|m| usize::from_str(m.as_str()).unwrap())
-                    .collect::<Vec<_>>(),
-            )
-        })
-        .unwrap();
-
-    let range_regex = Regex::new("([0-9]+) ([0-9]+) ([0-9]+)").unwrap();
-    let (src, dst) = lines.fold((seeds, Vec::new()), |(mut src, mut dst), l| {
-        if l.is_empty() {
-            return (src, dst);
-        }
-        if l.contains("map") {
-            println!("end {l} => {src:?} {dst:?}");
-            // New section, drain dst back to src. Things still in src are the
-            // same value.
-            src.extend(dst.drain(..));
-            return (src, dst);
-        }
-        let captures = range_regex.captures(&l).unwrap();
-        let get_val = |i: usize| usize::from_str(captures.get(i).unwrap().as_str()).unwrap();
-        let dst_start = get_val(1);
-        let src_start = get_val(2);
-        let len = get_val(3);
-        src.retain(|src| {
-            let src = *src;
-            let mapped = src
-                .checked_sub(src_start)
-                .and_then(|delta| (delta < len).then_some(dst_start + delta));
-            if let Some(mapped) = mapped {
-                println!("{l} {src} => {mapped}");
-                dst.push(mapped);
-                false
-            } else {
-                true
-            }
-        });
-        (src, dst)
-    });
-    let ans = src.into_iter().chain(dst.into_iter()).min().unwrap();
-    println!("day 5 part 1 = {ans}");
+
+use crate::{parse, IntervalSet, ParseError, Solution};
+
+pub struct Mapping {
+    dst_start: usize,
+    src_start: usize,
+    len: usize,
 }
 
-#[test]
-fn part_2() {
-    let mut lines = INPUT.lines();
-    let numbers_regex = Regex::new("[0-9]+").unwrap();
-    let seeds = lines
-        .by_ref()
-        .find_map(|l| {
-            if !l.starts_with("seeds:") {
-                return None;
-            }
-            Some(
-                numbers_regex
-                    .find_iter(l)
-                    .tuples()
-                    .map(|(start, end)| {
-                        let start = usize::from_str(start.as_str()).unwrap();
-                        let end = start + usize::from_str(end.as_str()).unwrap();
-                        start..end
-                    })
-                    .collect::<Vec<_>>(),
-            )
-        })
-        .unwrap();
-
-    let range_regex = Regex::new("([0-9]+) ([0-9]+) ([0-9]+)").unwrap();
-    let (src, dst) = lines.fold((seeds, Vec::new()), |(mut src, mut dst), l| {
-        if l.is_empty() {
-            return (src, dst);
-        }
-        if l.contains("map") {
-            // New section, drain dst back to src. Things still in src are the
-            // same value.
-            src.extend(dst.drain(..));
-            return (src, dst);
-        }
-        let captures = range_regex.captures(&l).unwrap();
-        let get_val = |i: usize| usize::from_str(captures.get(i).unwrap().as_str()).unwrap();
-        let dst_start = get_val(1);
-        let src_start = get_val(2);
-        let len = get_val(3);
-        let src_end = src_start + len;
-        let src_range = src_start..src_end;
-        let mut split = Vec::new();
-        src.retain_mut(|src| {
-            let map_start = cmp::max(src.start, src_range.start);
-            let map_end = cmp::min(src.end, src_range.end);
-            // Disjoint.
-            if map_end <= map_start {
-                return true;
+pub struct Almanac {
+    seeds: Vec<usize>,
+    maps: Vec<(String, Vec<Mapping>)>,
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Almanac;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "If You Give A Seed A Fertilizer";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = parse::lines(Self::DAY, input);
+        let numbers_regex = Regex::new("[0-9]+").unwrap();
+        let l = lines.expect("seeds")?;
+        let seeds = l
+            .text
+            .strip_prefix("seeds:")
+            .ok_or_else(|| l.error(l.text, "expected \"seeds:\""))?;
+        let seeds = numbers_regex
+            .find_iter(seeds)
+            .map(|m| l.parse(m.as_str()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let range_regex = Regex::new("^([0-9]+) ([0-9]+) ([0-9]+)$").unwrap();
+        let mut maps: Vec<(String, Vec<Mapping>)> = Vec::new();
+        for l in lines {
+            if l.text.is_empty() {
+                continue;
             }
-            // Push mapped.
-            let start = map_start - src_range.start + dst_start;
-            let end = map_end - src_range.start + dst_start;
-            dst.push(start..end);
-
-            let orig_src = src.clone();
-            let extra_left = orig_src.start < map_start;
-            if extra_left {
-                // Part of the range is to the left. Mutate the end.
-                src.end = map_start;
+            if l.text.contains("map") {
+                maps.push((l.text.to_string(), Vec::new()));
+                continue;
             }
-            let extra_right = orig_src.end > map_end;
-            if extra_right {
-                if extra_left {
-                    // Twice split.
-                    split.push(map_end..orig_src.end);
-                } else {
-                    // Once split.
-                    src.start = map_end;
+            let captures = l.captures(&range_regex)?;
+            let get_val = |i: usize| l.parse(captures.get(i).unwrap().as_str());
+            let (_, mappings) = maps
+                .last_mut()
+                .ok_or_else(|| l.error(l.text, "mapping before map header"))?;
+            mappings.push(Mapping {
+                dst_start: get_val(1)?,
+                src_start: get_val(2)?,
+                len: get_val(3)?,
+            });
+        }
+        Ok(Almanac { seeds, maps })
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        let (src, dst) = input.maps.iter().fold(
+            (input.seeds.clone(), Vec::new()),
+            |(mut src, mut dst), (name, mappings)| {
+                log::debug!("end {name} => {src:?} {dst:?}");
+                // New section, drain dst back to src. Things still in src are
+                // the same value.
+                src.append(&mut dst);
+                for Mapping {
+                    dst_start,
+                    src_start,
+                    len,
+                } in mappings
+                {
+                    src.retain(|src| {
+                        let src = *src;
+                        let mapped = src
+                            .checked_sub(*src_start)
+                            .and_then(|delta| (delta < *len).then_some(dst_start + delta));
+                        if let Some(mapped) = mapped {
+                            log::trace!("{dst_start} {src_start} {len} {src} => {mapped}");
+                            dst.push(mapped);
+                            false
+                        } else {
+                            true
+                        }
+                    });
                 }
-            }
-            extra_left || extra_right
+                (src, dst)
+            },
+        );
+        src.into_iter().chain(dst).min().unwrap()
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let seeds = input
+            .seeds
+            .iter()
+            .tuples()
+            .map(|(start, len)| *start..(start + len))
+            .collect::<IntervalSet<_>>();
+
+        input
+            .maps
+            .iter()
+            .fold(seeds, |seeds, (_, mappings)| {
+                seeds.offset_map(
+                    mappings
+                        .iter()
+                        .map(|m| (m.src_start..m.src_start + m.len, m.dst_start)),
+                )
+            })
+            .min()
+            .unwrap()
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day5::part_1(&Day5::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day5::part_2(&Day5::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(5, |input| {
+            crate::answers::check(5, 1, super::part_1(input).unwrap());
         });
-        src.extend(split.into_iter());
-        (src, dst)
-    });
-    let ans = src
-        .into_iter()
-        .chain(dst.into_iter())
-        .map(|r| r.start)
-        .min()
-        .unwrap();
-    println!("day 5 part 2 = {ans}");
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(5, |input| {
+            crate::answers::check(5, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 35);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 46);
+    }
 }
@@ -1,74 +1,10 @@
+use itertools::Itertools;
 use regex::Regex;
 use std::str::FromStr;
 
-const INPUT: &str = std::include_str!("input/day6.txt");
-
-#[test]
-fn part_1() {
-    let regex = Regex::new("[0-9]+").unwrap();
-    let mut lines = INPUT.lines();
-    let time = lines.next().unwrap();
-    let distance = lines.next().unwrap();
-    let time = regex
-        .find_iter(time)
-        .map(|m| i32::from_str(m.as_str()).unwrap());
-    let distance = regex
-        .find_iter(distance)
-        .map(|m| i32::from_str(m.as_str()).unwrap());
-    let ans: i32 = std::iter::zip(time, distance)
-        .map(|(time, distance)| {
-            // D < (T-H)* H
-            // H^2 -T*H + D > 0
-            // (-b +- sqrt(b^2 - 4 * a * c))/(2*a)
-            // (T +- sqrt(T^2 - 4*D)/2)
-            let t = f64::from(time);
-            let d = f64::from(distance);
-            let delta = t * t - 4f64 * d;
-            assert!(delta > 0f64);
-            let delta = f64::sqrt(delta);
-            let left = f64::ceil((t - delta) / 2f64) as i32;
-            let right = f64::floor((t + delta) / 2f64) as i32;
-
-            let travel = |x: i32| (time - x) * x;
-            let left = if travel(left) == distance {
-                left + 1
-            } else {
-                left
-            };
-            let right = if travel(right) == distance {
-                right - 1
-            } else {
-                right
-            };
-            let wins = right - left + 1;
-
-            println!(
-                "{time} {distance} [{left} {right} {wins}] {} {}",
-                travel(left),
-                travel(right)
-            );
-            wins
-        })
-        .product();
-    println!("day 6 part 1 = {ans}");
-}
-
-#[test]
-fn part_2() {
-    let regex = Regex::new("[0-9]+").unwrap();
-    let mut lines = INPUT.lines();
-    let time = lines.next().unwrap().replace(" ", "");
-    let distance = lines.next().unwrap().replace(" ", "");
-
-    let time = regex
-        .find(time.as_str())
-        .map(|m| i64::from_str(m.as_str()).unwrap())
-        .unwrap();
-    let distance = regex
-        .find(distance.as_str())
-        .map(|m| i64::from_str(m.as_str()).unwrap())
-        .unwrap();
+use crate::{parse, ParseError, Solution};
 
+fn wins(time: i64, distance: i64) -> i64 {
     // D < (T-H)* H
     // H^2 -T*H + D > 0
     // (-b +- sqrt(b^2 - 4 * a * c))/(2*a)
@@ -94,12 +30,90 @@ fn part_2() {
     };
     let wins = right - left + 1;
 
-    println!(
+    log::debug!(
         "{time} {distance} [{left} {right} {wins}] {} {}",
         travel(left),
         travel(right)
     );
-    let ans = wins;
+    wins
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    /// Race times and record distances.
+    type Input = (Vec<i64>, Vec<i64>);
+    type Part1 = i64;
+    type Part2 = i64;
+
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Wait For It";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let regex = Regex::new("[0-9]+").unwrap();
+        let mut lines = parse::lines(Self::DAY, input);
+        let mut numbers = |what: &str| {
+            let l = lines.expect(what)?;
+            let (_, numbers) = l.split_once(l.text, ":")?;
+            regex
+                .find_iter(numbers)
+                .map(|m| l.parse(m.as_str()))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let time = numbers("times")?;
+        let distance = numbers("distances")?;
+        Ok((time, distance))
+    }
+
+    fn part_1((time, distance): &Self::Input) -> Self::Part1 {
+        std::iter::zip(time, distance)
+            .map(|(time, distance)| wins(*time, *distance))
+            .product()
+    }
+
+    fn part_2((time, distance): &Self::Input) -> Self::Part2 {
+        // Each line is a single number once the spaces are removed.
+        let join = |v: &[i64]| i64::from_str(&v.iter().map(|v| v.to_string()).join("")).unwrap();
+        wins(join(time), join(distance))
+    }
+}
+
+pub fn part_1(input: &str) -> Result<i64, ParseError> {
+    Ok(Day6::part_1(&Day6::parse(input)?))
+}
+
+pub fn part_2(input: &str) -> Result<i64, ParseError> {
+    Ok(Day6::part_2(&Day6::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(6, |input| {
+            crate::answers::check(6, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(6, |input| {
+            crate::answers::check(6, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 288);
+    }
 
-    println!("day 6 part 2 = {ans}");
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 71503);
+    }
 }
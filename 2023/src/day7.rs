@@ -1,11 +1,9 @@
-use std::{cmp, collections::HashMap, fmt::Debug, str::FromStr};
+use std::{cmp, collections::HashMap, fmt::Debug};
 
-use itertools::Itertools;
+use crate::{parse, ParseError, Solution};
 
-const INPUT: &str = std::include_str!("input/day7.txt");
-
-#[derive(Ord, PartialEq, PartialOrd, Eq, Hash)]
-enum Card {
+#[derive(Ord, PartialEq, PartialOrd, Eq, Hash, Copy, Clone)]
+pub enum Card {
     A,
     K,
     Q,
@@ -22,9 +20,11 @@ enum Card {
     W,
 }
 
-impl From<char> for Card {
-    fn from(value: char) -> Self {
-        match value {
+impl TryFrom<char> for Card {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Ok(match value {
             '2' => Self::C2,
             '3' => Self::C3,
             '4' => Self::C4,
@@ -38,8 +38,8 @@ impl From<char> for Card {
             'Q' => Self::Q,
             'K' => Self::K,
             'A' => Self::A,
-            c => panic!("invalid {c}"),
-        }
+            c => return Err(c),
+        })
     }
 }
 
@@ -64,22 +64,7 @@ impl Debug for Card {
     }
 }
 
-type Game = [Card; 5];
-
-fn input(f: impl Fn(Card) -> Card) -> impl Iterator<Item = (Game, usize)> {
-    INPUT.lines().map(move |l| {
-        let (cards, value) = l.split_once(" ").unwrap();
-        assert_eq!(cards.len(), 5);
-        let (a, b, c, d, e) = cards
-            .chars()
-            .map(|c| f(Card::from(c)))
-            .collect_tuple()
-            .unwrap();
-        let game = [a, b, c, d, e];
-        let value = usize::from_str(value).unwrap();
-        (game, value)
-    })
-}
+pub type Game = [Card; 5];
 
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
 enum Outcome {
@@ -125,9 +110,13 @@ impl Outcome {
     }
 }
 
-fn puzzle(f: impl Fn(Card) -> Card) -> usize {
-    let mut vec = input(f)
-        .map(|(game, value)| (Outcome::from_game(&game), game, value))
+fn puzzle(input: &[(Game, usize)], f: impl Fn(Card) -> Card) -> usize {
+    let mut vec = input
+        .iter()
+        .map(|(game, value)| {
+            let game = game.map(&f);
+            (Outcome::from_game(&game), game, value)
+        })
         .collect::<Vec<_>>();
     vec.sort_by(|(outcome, game, _), (o, g, _)| cmp::Ord::cmp(&(outcome, game), &(o, g)).reverse());
     vec.iter()
@@ -141,17 +130,81 @@ fn puzzle(f: impl Fn(Card) -> Card) -> usize {
         .sum()
 }
 
-#[test]
-fn part1() {
-    let ans = puzzle(|c| c);
-    println!("day 7 part 1 = {ans}");
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<(Game, usize)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Camel Cards";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(Self::DAY, input)
+            .map(|l| {
+                let (cards, value) = l.split_once(l.text, " ")?;
+                let game = l
+                    .chars_in(cards, |c| Card::try_from(c).ok())
+                    .collect::<Result<Vec<_>, _>>()?;
+                let game = Game::try_from(game).map_err(|_| l.error(cards, "expected 5 cards"))?;
+                let value = l.parse(value)?;
+                Ok((game, value))
+            })
+            .collect()
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        puzzle(input, |c| c)
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        puzzle(input, |c| match c {
+            Card::J => Card::W,
+            c => c,
+        })
+    }
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day7::part_1(&Day7::parse(input)?))
 }
 
-#[test]
-fn part2() {
-    let ans = puzzle(|c| match c {
-        Card::J => Card::W,
-        c => c,
-    });
-    println!("day 7 part 2 = {ans}");
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(Day7::part_2(&Day7::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(7, |input| {
+            crate::answers::check(7, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(7, |input| {
+            crate::answers::check(7, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 6440);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 5905);
+    }
 }
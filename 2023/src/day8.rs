@@ -2,101 +2,159 @@ use std::collections::HashMap;
 
 use itertools::{FoldWhile, Itertools};
 
-const INPUT: &str = std::include_str!("input/day8.txt");
+use crate::{parse, ParseError, Solution};
 
 #[derive(Debug)]
-enum Dir {
+pub enum Dir {
     Left,
     Right,
 }
 
-impl From<char> for Dir {
-    fn from(value: char) -> Self {
+impl Dir {
+    fn new(value: char) -> Option<Self> {
         match value {
-            'L' => Self::Left,
-            'R' => Self::Right,
-            o => panic!("{o}"),
+            'L' => Some(Self::Left),
+            'R' => Some(Self::Right),
+            _ => None,
         }
     }
 }
 
-fn input() -> (Vec<Dir>, HashMap<String, (String, String)>) {
-    let mut lines = INPUT.lines();
-    let dirs = lines
-        .next()
-        .unwrap()
-        .chars()
-        .map(|c| Dir::from(c))
-        .collect::<Vec<_>>();
-
-    let regex = regex::Regex::new(r"([0-9A-Z]{3}) = \(([0-9A-Z]{3}), ([0-9A-Z]{3})\)").unwrap();
-    let map = lines
-        .filter_map(|l| {
-            if l.is_empty() {
-                return None;
-            }
-            let captures = regex.captures(l).unwrap_or_else(|| panic!("{l}"));
-            let key = captures.get(1).unwrap().as_str().to_string();
-            let left = captures.get(2).unwrap().as_str().to_string();
-            let right = captures.get(3).unwrap().as_str().to_string();
-            Some((key, (left, right)))
-        })
-        .collect::<HashMap<_, _>>();
-    (dirs, map)
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = (Vec<Dir>, HashMap<String, (String, String)>);
+    type Part1 = usize;
+    type Part2 = u64;
+
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Haunted Wasteland";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut lines = parse::lines(Self::DAY, input);
+        let dirs = lines
+            .expect("directions")?
+            .chars(Dir::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let regex =
+            regex::Regex::new(r"^([0-9A-Z]{3}) = \(([0-9A-Z]{3}), ([0-9A-Z]{3})\)$").unwrap();
+        let map = lines
+            .filter(|l| !l.text.is_empty())
+            .map(|l| {
+                let captures = l.captures(&regex)?;
+                let key = captures.get(1).unwrap().as_str().to_string();
+                let left = captures.get(2).unwrap().as_str().to_string();
+                let right = captures.get(3).unwrap().as_str().to_string();
+                Ok((key, (left, right)))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok((dirs, map))
+    }
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        let (dir, map) = input;
+        let mut dir = std::iter::repeat(dir.iter()).flatten();
+
+        let (_, steps) = dir
+            .fold_while(("AAA", 0), |(cur, count), dir| {
+                let count = count + 1;
+                let m = map.get(cur).unwrap();
+                let nxt = match dir {
+                    Dir::Left => &m.0,
+                    Dir::Right => &m.1,
+                };
+                if nxt == "ZZZ" {
+                    FoldWhile::Done((nxt, count))
+                } else {
+                    FoldWhile::Continue((nxt, count))
+                }
+            })
+            .into_inner();
+
+        steps
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        let (dir, map) = input;
+
+        map.keys()
+            .filter(|k| k.ends_with("A"))
+            .map(|start| {
+                let mut dir = std::iter::repeat(dir.iter()).flatten();
+
+                let (_, steps) = dir
+                    .fold_while((start, 0u64), |(cur, count), dir| {
+                        let count = count + 1;
+                        let m = map.get(cur).unwrap();
+                        let nxt = match dir {
+                            Dir::Left => &m.0,
+                            Dir::Right => &m.1,
+                        };
+                        if nxt.ends_with("Z") {
+                            FoldWhile::Done((nxt, count))
+                        } else {
+                            FoldWhile::Continue((nxt, count))
+                        }
+                    })
+                    .into_inner();
+                steps
+            })
+            .fold(1, num::integer::lcm)
+    }
 }
 
-#[test]
-fn part1() {
-    let (dir, map) = input();
-    let mut dir = std::iter::repeat(dir.iter()).flatten();
-
-    let (_, steps) = dir
-        .fold_while(("AAA", 0), |(cur, count), dir| {
-            let count = count + 1;
-            let m = map.get(cur).unwrap();
-            let nxt = match dir {
-                Dir::Left => &m.0,
-                Dir::Right => &m.1,
-            };
-            if nxt == "ZZZ" {
-                FoldWhile::Done((nxt, count))
-            } else {
-                FoldWhile::Continue((nxt, count))
-            }
-        })
-        .into_inner();
-
-    println!("day 8 part 1 = {steps}");
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(Day8::part_1(&Day8::parse(input)?))
 }
 
-#[test]
-fn part2() {
-    let (dir, map) = input();
-
-    let steps = map
-        .keys()
-        .filter(|k| k.ends_with("A"))
-        .map(|start| {
-            let mut dir = std::iter::repeat(dir.iter()).flatten();
-
-            let (_, steps) = dir
-                .fold_while((start, 0u64), |(cur, count), dir| {
-                    let count = count + 1;
-                    let m = map.get(cur).unwrap();
-                    let nxt = match dir {
-                        Dir::Left => &m.0,
-                        Dir::Right => &m.1,
-                    };
-                    if nxt.ends_with("Z") {
-                        FoldWhile::Done((nxt, count))
-                    } else {
-                        FoldWhile::Continue((nxt, count))
-                    }
-                })
-                .into_inner();
-            steps
-        })
-        .fold(1, |l, v| num::integer::lcm(l, v));
-
-    println!("day 8 part 2 = {steps}");
+pub fn part_2(input: &str) -> Result<u64, ParseError> {
+    Ok(Day8::part_2(&Day8::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE_PART_1: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+    const EXAMPLE_PART_2: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(8, |input| {
+            crate::answers::check(8, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(8, |input| {
+            crate::answers::check(8, 2, super::part_2(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE_PART_1).unwrap(), 6);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE_PART_2).unwrap(), 6);
+    }
 }
@@ -1,57 +1,107 @@
-use std::str::FromStr;
-
-const INPUT: &str = std::include_str!("input/day9.txt");
-
-#[test]
-fn part_1() {
-    fn diffs(iter: &mut dyn Iterator<Item = i64>) -> i64 {
-        let mut f = if let Some(f) = iter.next() {
-            f
-        } else {
-            return 0;
-        };
-        let mut niter = iter.scan(&mut f, |s, n| {
-            let nn = n - **s;
-            **s = n;
-            Some(nn)
-        });
-        let up = diffs(&mut niter);
-        up + f
+use crate::{parse, ParseError, Solution};
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Vec<i64>>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Mirage Maintenance";
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::lines(Self::DAY, input)
+            .map(|l| l.text.split(" ").map(|item| l.parse(item)).collect())
+            .collect()
     }
-    let ans: i64 = INPUT
-        .lines()
-        .map(|l| {
-            let mut items = l.split(" ").map(|item| i64::from_str(item).unwrap());
-            diffs(&mut items)
-        })
-        .sum();
-
-    println!("day 9 part 1 = {ans}");
+
+    fn part_1(input: &Self::Input) -> Self::Part1 {
+        fn diffs(iter: &mut dyn Iterator<Item = i64>) -> i64 {
+            let mut f = if let Some(f) = iter.next() {
+                f
+            } else {
+                return 0;
+            };
+            let mut niter = iter.scan(&mut f, |s, n| {
+                let nn = n - **s;
+                **s = n;
+                Some(nn)
+            });
+            let up = diffs(&mut niter);
+            up + f
+        }
+        input
+            .iter()
+            .map(|l| {
+                let mut items = l.iter().copied();
+                diffs(&mut items)
+            })
+            .sum()
+    }
+
+    fn part_2(input: &Self::Input) -> Self::Part2 {
+        fn diffs(iter: &mut dyn Iterator<Item = i64>) -> i64 {
+            let f = if let Some(f) = iter.next() {
+                f
+            } else {
+                return 0;
+            };
+            let mut niter = iter.scan(f, |s, n| {
+                let nn = n - *s;
+                *s = n;
+                Some(nn)
+            });
+            let up = diffs(&mut niter);
+            f - up
+        }
+        input
+            .iter()
+            .map(|l| {
+                let mut items = l.iter().copied();
+                diffs(&mut items)
+            })
+            .sum()
+    }
+}
+
+pub fn part_1(input: &str) -> Result<i64, ParseError> {
+    Ok(Day9::part_1(&Day9::parse(input)?))
 }
 
-#[test]
-fn part_2() {
-    fn diffs(iter: &mut dyn Iterator<Item = i64>) -> i64 {
-        let f = if let Some(f) = iter.next() {
-            f
-        } else {
-            return 0;
-        };
-        let mut niter = iter.scan(f, |s, n| {
-            let nn = n - *s;
-            *s = n;
-            Some(nn)
+pub fn part_2(input: &str) -> Result<i64, ParseError> {
+    Ok(Day9::part_2(&Day9::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn part_1() {
+        crate::input::with_input(9, |input| {
+            crate::answers::check(9, 1, super::part_1(input).unwrap());
+        });
+    }
+
+    #[test]
+    fn part_2() {
+        crate::input::with_input(9, |input| {
+            crate::answers::check(9, 2, super::part_2(input).unwrap());
         });
-        let up = diffs(&mut niter);
-        f - up
     }
-    let ans: i64 = INPUT
-        .lines()
-        .map(|l| {
-            let mut items = l.split(" ").map(|item| i64::from_str(item).unwrap());
-            diffs(&mut items)
-        })
-        .sum();
-
-    println!("day 9 part 2 = {ans}");
+
+    #[test]
+    fn example_part_1() {
+        assert_eq!(super::part_1(EXAMPLE).unwrap(), 114);
+    }
+
+    #[test]
+    fn example_part_2() {
+        assert_eq!(super::part_2(EXAMPLE).unwrap(), 2);
+    }
 }
@@ -0,0 +1,939 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{Direction, Vector};
+
+/// A random input for `day` that its solver accepts, the same `seed` always
+/// gives the same input.
+pub fn input(day: u8, seed: u64) -> Option<String> {
+    let generate = match day {
+        1 => day1,
+        2 => day2,
+        3 => day3,
+        4 => day4,
+        5 => day5,
+        6 => day6,
+        7 => day7,
+        8 => day8,
+        9 => day9,
+        10 => day10,
+        11 => day11,
+        12 => day12,
+        13 => day13,
+        14 => day14,
+        15 => day15,
+        16 => day16,
+        17 => day17,
+        18 => day18,
+        19 => day19,
+        20 => day20,
+        21 => day21,
+        22 => day22,
+        23 => day23,
+        24 => day24,
+        25 => day25,
+        _ => return None,
+    };
+    Some(generate(&mut ChaCha8Rng::seed_from_u64(seed)))
+}
+
+fn lines(lines: impl IntoIterator<Item = impl std::fmt::Display>) -> String {
+    lines.into_iter().map(|l| format!("{l}\n")).collect()
+}
+
+/// Rows of `cols` characters picked by `f`.
+fn grid(cols: usize, rows: usize, mut f: impl FnMut(Vector) -> char) -> String {
+    lines((0..rows).map(|y| {
+        (0..cols)
+            .map(|x| f(Vector::new(x as isize, y as isize)))
+            .collect::<String>()
+    }))
+}
+
+/// A name of `len` characters from `alphabet` not in `taken`.
+fn name(rng: &mut ChaCha8Rng, len: usize, alphabet: &[u8], taken: &mut HashSet<String>) -> String {
+    loop {
+        let name = (0..len)
+            .map(|_| *alphabet.choose(rng).unwrap() as char)
+            .collect::<String>();
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+/// A polyomino in a `cols` by `lines` box, without holes and without cells
+/// touching only by a corner so its outline is a single simple loop.
+fn polyomino(rng: &mut ChaCha8Rng, cols: isize, lines: isize) -> HashSet<Vector> {
+    let valid = |cells: &HashSet<Vector>| {
+        let inside = |x, y| cells.contains(&Vector::new(x, y));
+        let pinched = (-1..lines).any(|y| {
+            (-1..cols).any(|x| {
+                let (a, b) = (inside(x, y), inside(x + 1, y));
+                let (c, d) = (inside(x, y + 1), inside(x + 1, y + 1));
+                a == d && b == c && a != b
+            })
+        });
+        let outside = crate::search::bfs(
+            [Vector::new(-1, -1)],
+            |v| {
+                let v = *v;
+                Direction::all()
+                    .map(move |d| v + d.to_vector())
+                    .filter(|n| (-1..=cols).contains(&n.x) && (-1..=lines).contains(&n.y))
+                    .filter(|n| !cells.contains(n))
+            },
+            None,
+        );
+        let area = (cols + 2) * (lines + 2);
+        !pinched && outside.distances.len() + cells.len() == area as usize
+    };
+
+    let mut cells = HashSet::from([Vector::new(rng.gen_range(0..cols), rng.gen_range(0..lines))]);
+    let target = (cols * lines / 2) as usize;
+    for _ in 0..cols * lines * 20 {
+        if cells.len() >= target {
+            break;
+        }
+        let from = *cells.iter().sorted_by_key(|v| (v.y, v.x)).collect_vec()
+            [rng.gen_range(0..cells.len())];
+        let next = from
+            + Direction::all()
+                .collect_vec()
+                .choose(rng)
+                .unwrap()
+                .to_vector();
+        if !(0..cols).contains(&next.x) || !(0..lines).contains(&next.y) || !cells.insert(next) {
+            continue;
+        }
+        if !valid(&cells) {
+            cells.remove(&next);
+        }
+    }
+    cells
+}
+
+/// Corners of the unit squares of `cells` walked clockwise around their
+/// outline, one unit step at a time.
+fn outline(cells: &HashSet<Vector>) -> Vec<Vector> {
+    let mut edges = HashMap::new();
+    for cell in cells {
+        let corners = [(0, 0), (1, 0), (1, 1), (0, 1)].map(|(x, y)| *cell + Vector::new(x, y));
+        let sides = [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+        ];
+        for (i, side) in sides.into_iter().enumerate() {
+            if !cells.contains(&(*cell + side.to_vector())) {
+                edges.insert(corners[i], corners[(i + 1) % 4]);
+            }
+        }
+    }
+    let start = *edges.keys().min_by_key(|v| (v.y, v.x)).unwrap();
+    let mut corners = vec![start];
+    let mut at = edges[&start];
+    while at != start {
+        corners.push(at);
+        at = edges[&at];
+    }
+    corners
+}
+
+fn day1(rng: &mut ChaCha8Rng) -> String {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    lines((0..rng.gen_range(5..20)).map(|_| {
+        let mut line = String::new();
+        for _ in 0..rng.gen_range(1..6) {
+            match rng.gen_range(0..3) {
+                0 => line.push(char::from_digit(rng.gen_range(1..10), 10).unwrap()),
+                1 => line.push_str(WORDS.choose(rng).unwrap()),
+                _ => line.extend((0..rng.gen_range(1..4)).map(|_| rng.gen_range('a'..='z'))),
+            }
+        }
+        if !line.chars().any(|c| c.is_ascii_digit()) {
+            let at = rng.gen_range(0..=line.len());
+            line.insert(at, char::from_digit(rng.gen_range(1..10), 10).unwrap());
+        }
+        line
+    }))
+}
+
+fn day2(rng: &mut ChaCha8Rng) -> String {
+    lines((1..rng.gen_range(5..20)).map(|id| {
+        let sets = (0..rng.gen_range(1..5))
+            .map(|_| {
+                let mut colours = ["red", "green", "blue"];
+                colours.shuffle(rng);
+                colours[..rng.gen_range(1..=3)]
+                    .iter()
+                    .map(|c| format!("{} {c}", rng.gen_range(1..=20)))
+                    .join(", ")
+            })
+            .join("; ");
+        format!("Game {id}: {sets}")
+    }))
+}
+
+fn day3(rng: &mut ChaCha8Rng) -> String {
+    let cols = rng.gen_range(10..30);
+    lines((0..rng.gen_range(10..30)).map(|_| {
+        let mut row = String::new();
+        while row.len() < cols {
+            let after_number = row.ends_with(|c: char| c.is_ascii_digit());
+            match rng.gen_range(0..10) {
+                0..=1 if !after_number => {
+                    let number = rng.gen_range(1..1000).to_string();
+                    row.push_str(&number[..number.len().min(cols - row.len())]);
+                }
+                2 => row.push(*b"*#+$/@=%&-".choose(rng).unwrap() as char),
+                _ => row.push('.'),
+            }
+        }
+        row
+    }))
+}
+
+fn day4(rng: &mut ChaCha8Rng) -> String {
+    let cards = rng.gen_range(5..20);
+    lines((1..=cards).map(|id| {
+        // Copies never go past the last card.
+        let matches = rng.gen_range(0..=5.min(cards - id));
+        let mut numbers = (1..100).collect_vec();
+        numbers.shuffle(rng);
+        let winning = &numbers[..5];
+        let mut have = winning[..matches]
+            .iter()
+            .chain(&numbers[5..13 - matches])
+            .copied()
+            .collect_vec();
+        have.shuffle(rng);
+        let list = |v: &[usize]| v.iter().map(|n| format!("{n:>2}")).join(" ");
+        format!("Card {id:>3}: {} | {}", list(winning), list(&have))
+    }))
+}
+
+fn day5(rng: &mut ChaCha8Rng) -> String {
+    const STAGES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+    let seeds = (0..rng.gen_range(2..5))
+        .map(|_| format!("{} {}", rng.gen_range(0..1000), rng.gen_range(1..50)))
+        .join(" ");
+    let mut out = vec![format!("seeds: {seeds}")];
+    for (src, dst) in STAGES.iter().tuple_windows() {
+        out.push(String::new());
+        out.push(format!("{src}-to-{dst} map:"));
+        let cuts = (0..rng.gen_range(2..8))
+            .map(|_| rng.gen_range(0..1100))
+            .sorted()
+            .dedup()
+            .collect_vec();
+        for (start, end) in cuts.into_iter().tuple_windows() {
+            if rng.gen_bool(0.7) {
+                out.push(format!(
+                    "{} {start} {}",
+                    rng.gen_range(0..1000),
+                    end - start
+                ));
+            }
+        }
+    }
+    lines(out)
+}
+
+fn day6(rng: &mut ChaCha8Rng) -> String {
+    // Two digit times and at most three digit records keep the joined race
+    // of part 2 winnable once there are three races.
+    let races = (0..rng.gen_range(3..=4))
+        .map(|_| {
+            let time = rng.gen_range(10..100);
+            (time, rng.gen_range(1..=((time * time - 1) / 4).min(999)))
+        })
+        .collect_vec();
+    let row = |f: fn(&(u32, u32)) -> u32| races.iter().map(|r| format!("{:>5}", f(r))).join("");
+    lines([
+        format!("Time:    {}", row(|r| r.0)),
+        format!("Distance:{}", row(|r| r.1)),
+    ])
+}
+
+fn day7(rng: &mut ChaCha8Rng) -> String {
+    lines((0..rng.gen_range(5..50)).map(|_| {
+        let hand = (0..5)
+            .map(|_| *b"23456789TJQKA".choose(rng).unwrap() as char)
+            .collect::<String>();
+        format!("{hand} {}", rng.gen_range(1..=1000))
+    }))
+}
+
+fn day8(rng: &mut ChaCha8Rng) -> String {
+    // Each ghost walks a loop as long as a prime number of passes over the
+    // directions, and reaches its Z node only at the end of the loop, which
+    // is what the least common multiple of part 2 relies on.
+    let dirs = (0..rng.gen_range(5..12))
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect_vec();
+    let mut primes = [3, 5, 7, 11, 13];
+    primes.shuffle(rng);
+    let alphabet = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let mut taken = HashSet::new();
+    let ghosts = primes[..rng.gen_range(2..=4)]
+        .iter()
+        .enumerate()
+        .map(|(g, prime)| {
+            let (start, end) = if g == 0 {
+                ("AAA".to_string(), "ZZZ".to_string())
+            } else {
+                let prefix = loop {
+                    let prefix = name(rng, 2, alphabet, &mut taken);
+                    if prefix != "AA" && prefix != "ZZ" {
+                        break prefix;
+                    }
+                };
+                (format!("{prefix}A"), format!("{prefix}Z"))
+            };
+            let len = dirs.len() * prime;
+            let mut path = vec![start];
+            path.extend((1..len).map(|_| {
+                let mut n = name(rng, 2, alphabet, &mut taken);
+                n.push(*b"BCDEFGHIJKLMNOPQRSTUVWXY".choose(rng).unwrap() as char);
+                n
+            }));
+            path.push(end);
+            path
+        })
+        .collect_vec();
+    let all = ghosts.iter().flatten().cloned().collect_vec();
+    let mut nodes = Vec::new();
+    for path in &ghosts {
+        let len = path.len() - 1;
+        for (i, node) in path.iter().enumerate() {
+            let next = path[i % len + 1].clone();
+            let other = all.choose(rng).unwrap().clone();
+            let (left, right) = match dirs[i % dirs.len()] {
+                'L' => (next, other),
+                _ => (other, next),
+            };
+            nodes.push(format!("{node} = ({left}, {right})"));
+        }
+    }
+    nodes.shuffle(rng);
+    let mut out = vec![dirs.into_iter().collect::<String>(), String::new()];
+    out.extend(nodes);
+    lines(out)
+}
+
+fn day9(rng: &mut ChaCha8Rng) -> String {
+    lines((0..rng.gen_range(5..20)).map(|_| {
+        let coefficients = (0..=rng.gen_range(0..=5))
+            .map(|_| rng.gen_range(-5i64..=5))
+            .collect_vec();
+        (0..21i64)
+            .map(|x| coefficients.iter().fold(0, |acc, c| acc * x + c))
+            .join(" ")
+    }))
+}
+
+fn day10(rng: &mut ChaCha8Rng) -> String {
+    // Corners of the outline sit on odd tiles and the pipes between them on
+    // even ones, with a tile of junk all around.
+    let (cols, lines) = (rng.gen_range(4..10), rng.gen_range(4..10));
+    let corners = outline(&polyomino(rng, cols, lines));
+    let tiles = corners
+        .iter()
+        .circular_tuple_windows()
+        .flat_map(|(a, b)| [*a * 2, *a + *b])
+        .map(|v| v + Vector::new(1, 1))
+        .collect_vec();
+    let mut pipes = HashMap::new();
+    for (prev, at, next) in tiles.iter().circular_tuple_windows() {
+        let ends = [*prev - *at, *next - *at].map(|v| Direction::from_vector(v).unwrap());
+        let pipe = match ends.into_iter().sorted().collect_tuple() {
+            Some((Direction::Up, Direction::Down)) => '|',
+            Some((Direction::Left, Direction::Right)) => '-',
+            Some((Direction::Up, Direction::Right)) => 'L',
+            Some((Direction::Up, Direction::Left)) => 'J',
+            Some((Direction::Down, Direction::Left)) => '7',
+            Some((Direction::Down, Direction::Right)) => 'F',
+            o => unreachable!("not a pipe {o:?}"),
+        };
+        pipes.insert(*at, pipe);
+    }
+    // Junk next to the animal could lead the solver round a loop of its
+    // own.
+    let animal = *tiles.choose(rng).unwrap();
+    pipes.insert(animal, 'S');
+    let near_animal = Direction::all()
+        .map(|d| animal + d.to_vector())
+        .collect::<HashSet<_>>();
+    grid(
+        2 * cols as usize + 3,
+        2 * lines as usize + 3,
+        |v| match pipes.get(&v) {
+            Some(pipe) => *pipe,
+            None if near_animal.contains(&v) || rng.gen_bool(0.4) => '.',
+            None => *b"|-LJ7F".choose(rng).unwrap() as char,
+        },
+    )
+}
+
+fn day11(rng: &mut ChaCha8Rng) -> String {
+    let (cols, rows) = (rng.gen_range(8..20), rng.gen_range(8..20));
+    grid(cols, rows, |_| if rng.gen_bool(0.1) { '#' } else { '.' })
+}
+
+fn day12(rng: &mut ChaCha8Rng) -> String {
+    lines((0..rng.gen_range(5..15)).map(|_| {
+        let mut springs = (0..rng.gen_range(5..20))
+            .map(|_| rng.gen_bool(0.4))
+            .collect_vec();
+        if !springs.contains(&true) {
+            let at = rng.gen_range(0..springs.len());
+            springs[at] = true;
+        }
+        let groups = springs
+            .iter()
+            .dedup_with_count()
+            .filter(|(_, damaged)| **damaged)
+            .map(|(len, _)| len)
+            .join(",");
+        let row = springs
+            .iter()
+            .map(|damaged| match damaged {
+                _ if rng.gen_bool(0.5) => '?',
+                true => '#',
+                false => '.',
+            })
+            .collect::<String>();
+        format!("{row} {groups}")
+    }))
+}
+
+fn day13(rng: &mut ChaCha8Rng) -> String {
+    // Symmetric both ways, then one cell flipped in a row the horizontal
+    // mirror does not reach: the horizontal mirror stays clean for part 1
+    // and the vertical one is left with the smudge of part 2.
+    (0..rng.gen_range(3..8))
+        .map(|_| {
+            let (cols, rows) = (rng.gen_range(5..14), rng.gen_range(5..14));
+            let col_mirror = rng.gen_range(1..cols);
+            let row_mirror = loop {
+                let m = rng.gen_range(1..rows);
+                if 2 * m != rows {
+                    break m;
+                }
+            };
+            let mirrored = |at: usize, mirror: usize| (2 * mirror).checked_sub(at + 1);
+            let mut map = vec![vec![false; cols]; rows];
+            for row in map.iter_mut() {
+                for x in 0..cols {
+                    row[x] = match mirrored(x, col_mirror) {
+                        Some(m) if m < x => row[m],
+                        _ => rng.gen_bool(0.5),
+                    };
+                }
+            }
+            for y in 0..rows {
+                if let Some(m) = mirrored(y, row_mirror).filter(|m| *m < y) {
+                    map[y] = map[m].clone();
+                }
+            }
+            let reach = row_mirror.min(rows - row_mirror);
+            let y = *(0..row_mirror - reach)
+                .chain(row_mirror + reach..rows)
+                .collect_vec()
+                .choose(rng)
+                .unwrap();
+            let reach = col_mirror.min(cols - col_mirror);
+            let x = rng.gen_range(col_mirror - reach..col_mirror + reach);
+            map[y][x] = !map[y][x];
+            grid(cols, rows, |v| {
+                if map[v.y as usize][v.x as usize] {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+        })
+        .join("\n")
+}
+
+fn day14(rng: &mut ChaCha8Rng) -> String {
+    let size = rng.gen_range(6..16);
+    grid(size, size, |_| match rng.gen_range(0..20) {
+        0..=3 => 'O',
+        4..=6 => '#',
+        _ => '.',
+    })
+}
+
+fn day15(rng: &mut ChaCha8Rng) -> String {
+    let mut taken = HashSet::new();
+    let labels = (0..rng.gen_range(2..8))
+        .map(|_| {
+            let len = rng.gen_range(1..=3);
+            name(rng, len, LOWERCASE, &mut taken)
+        })
+        .collect_vec();
+    let steps = (0..rng.gen_range(10..40))
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            if rng.gen_bool(0.3) {
+                format!("{label}-")
+            } else {
+                format!("{label}={}", rng.gen_range(1..10))
+            }
+        })
+        .join(",");
+    lines([steps])
+}
+
+fn day16(rng: &mut ChaCha8Rng) -> String {
+    let (cols, rows) = (rng.gen_range(8..16), rng.gen_range(8..16));
+    grid(cols, rows, |_| match rng.gen_range(0..20) {
+        0 => '/',
+        1 => '\\',
+        2 => '-',
+        3 => '|',
+        _ => '.',
+    })
+}
+
+fn day17(rng: &mut ChaCha8Rng) -> String {
+    // Small enough that the ultra crucible can always go straight along the
+    // top and down the right side.
+    let (cols, rows) = (rng.gen_range(5..12), rng.gen_range(5..12));
+    grid(cols, rows, |_| {
+        char::from_digit(rng.gen_range(1..10), 10).unwrap()
+    })
+}
+
+fn day18(rng: &mut ChaCha8Rng) -> String {
+    // Outlines of two polyominoes stretched by random widths, the one with
+    // fewer sides has sides cut in two to match the other. Widths of at
+    // least 25 leave enough to cut even when the other has 100 sides.
+    fn trench(rng: &mut ChaCha8Rng, gaps: std::ops::Range<isize>) -> Vec<(Direction, isize)> {
+        let (cols, lines) = (rng.gen_range(3..8), rng.gen_range(3..8));
+        let corners = outline(&polyomino(rng, cols, lines));
+        let mut stretch = |n| {
+            (0..=n)
+                .scan(0, |at, _| {
+                    *at += rng.gen_range(gaps.clone());
+                    Some(*at)
+                })
+                .collect_vec()
+        };
+        let (xs, ys) = (stretch(cols), stretch(lines));
+        let at = |v: &Vector| Vector::new(xs[v.x as usize], ys[v.y as usize]);
+        corners
+            .iter()
+            .circular_tuple_windows()
+            .map(|(a, b)| {
+                let d = at(b) - at(a);
+                (
+                    Direction::from_vector(*b - *a).unwrap(),
+                    d.x.abs() + d.y.abs(),
+                )
+            })
+            .coalesce(|(d, a), (e, b)| {
+                if d == e {
+                    Ok((d, a + b))
+                } else {
+                    Err(((d, a), (e, b)))
+                }
+            })
+            .collect()
+    }
+    fn split(rng: &mut ChaCha8Rng, sides: &mut Vec<(Direction, isize)>, len: usize) {
+        while sides.len() < len {
+            let long = (0..sides.len()).filter(|i| sides[*i].1 > 1).collect_vec();
+            let i = *long.choose(rng).unwrap();
+            let (d, l) = sides[i];
+            let cut = rng.gen_range(1..l);
+            sides[i] = (d, cut);
+            sides.insert(i + 1, (d, l - cut));
+        }
+    }
+
+    let mut part_1 = trench(rng, 25..60);
+    let mut part_2 = trench(rng, 1000..50000);
+    let len = part_1.len().max(part_2.len());
+    split(rng, &mut part_1, len);
+    split(rng, &mut part_2, len);
+    lines(
+        part_1
+            .into_iter()
+            .zip(part_2)
+            .map(|((d, l), (colour_d, colour_l))| {
+                let d = match d {
+                    Direction::Right => 'R',
+                    Direction::Down => 'D',
+                    Direction::Left => 'L',
+                    _ => 'U',
+                };
+                let colour_d = match colour_d {
+                    Direction::Right => 0,
+                    Direction::Down => 1,
+                    Direction::Left => 2,
+                    _ => 3,
+                };
+                format!("{d} {l} (#{colour_l:05x}{colour_d})")
+            }),
+    )
+}
+
+fn day19(rng: &mut ChaCha8Rng) -> String {
+    // Workflows only send parts to workflows further down the list so they
+    // can't loop.
+    let mut taken = HashSet::from(["in".to_string()]);
+    let mut names = vec!["in".to_string()];
+    names.extend((1..rng.gen_range(4..12)).map(|_| {
+        let len = rng.gen_range(2..=3);
+        name(rng, len, LOWERCASE, &mut taken)
+    }));
+    let mut out = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let target = |rng: &mut ChaCha8Rng| match rng.gen_range(i..names.len() + 2) {
+                t if t == i || t == names.len() => "A".to_string(),
+                t if t == names.len() + 1 => "R".to_string(),
+                t => names[t].clone(),
+            };
+            let mut rules = (0..rng.gen_range(1..=4))
+                .map(|_| {
+                    let category = *b"xmas".choose(rng).unwrap() as char;
+                    let op = if rng.gen_bool(0.5) { '<' } else { '>' };
+                    let value = rng.gen_range(1..=4000);
+                    format!("{category}{op}{value}:{}", target(rng))
+                })
+                .collect_vec();
+            rules.push(target(rng));
+            format!("{name}{{{}}}", rules.join(","))
+        })
+        .collect_vec();
+    out.shuffle(rng);
+    out.push(String::new());
+    out.extend((0..rng.gen_range(5..20)).map(|_| {
+        let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=4000));
+        format!("{{x={x},m={m},a={a},s={s}}}")
+    }));
+    lines(out)
+}
+
+fn day20(rng: &mut ChaCha8Rng) -> String {
+    // Binary counters of flip-flops that a conjunction resets once they
+    // reach their number, inverted into the conjunction feeding rx, like
+    // the real inputs part 2 relies on, kept short enough to press the
+    // button until rx gets its pulse.
+    let mut taken = HashSet::from(["rx".to_string()]);
+    let last = name(rng, 2, LOWERCASE, &mut taken);
+    let mut out = vec![format!("&{last} -> rx")];
+    let mut starts = Vec::new();
+    for _ in 0..rng.gen_range(2..=3) {
+        let bits = rng.gen_range(3..=5);
+        let number = rng.gen_range(1usize << (bits - 1)..1 << bits) | 1;
+        let flops = (0..bits)
+            .map(|_| name(rng, 2, LOWERCASE, &mut taken))
+            .collect_vec();
+        let reset = name(rng, 2, LOWERCASE, &mut taken);
+        let inverter = name(rng, 2, LOWERCASE, &mut taken);
+        let set = |bit: usize| number & 1 << bit != 0;
+        for (bit, flop) in flops.iter().enumerate() {
+            let outputs = flops
+                .get(bit + 1)
+                .into_iter()
+                .chain(set(bit).then_some(&reset))
+                .join(", ");
+            out.push(format!("%{flop} -> {outputs}"));
+        }
+        let resets = (0..bits)
+            .filter(|bit| *bit == 0 || !set(*bit))
+            .map(|bit| &flops[bit])
+            .chain([&inverter])
+            .join(", ");
+        out.push(format!("&{reset} -> {resets}"));
+        out.push(format!("&{inverter} -> {last}"));
+        starts.push(flops[0].clone());
+    }
+    out.push(format!("broadcaster -> {}", starts.join(", ")));
+    out.shuffle(rng);
+    lines(out)
+}
+
+fn day21(rng: &mut ChaCha8Rng) -> String {
+    // The shortcut of part 2 needs a 131 tile square with the start in the
+    // middle, clear lanes through the start, along the edges and around the
+    // diamond the start reaches in 65 steps, and every plot reachable.
+    let size = 131;
+    let half = size / 2;
+    let clear = |v: Vector| {
+        let d = v - Vector::new(half, half);
+        v.x == half
+            || v.y == half
+            || v.x == 0
+            || v.y == 0
+            || v.x == size - 1
+            || v.y == size - 1
+            || (half - 1..=half + 1).contains(&(d.x.abs() + d.y.abs()))
+    };
+    let mut rocks = HashSet::new();
+    for y in 0..size {
+        for x in 0..size {
+            let v = Vector::new(x, y);
+            if !clear(v) && rng.gen_bool(0.12) {
+                rocks.insert(v);
+            }
+        }
+    }
+    let reached = crate::search::bfs(
+        [Vector::new(half, half)],
+        |v| {
+            let v = *v;
+            Direction::all()
+                .map(move |d| v + d.to_vector())
+                .filter(|n| (0..size).contains(&n.x) && (0..size).contains(&n.y))
+                .filter(|n| !rocks.contains(n))
+        },
+        None,
+    );
+    grid(size as usize, size as usize, |v| {
+        if v == Vector::new(half, half) {
+            'S'
+        } else if reached.distances.contains_key(&v) {
+            '.'
+        } else {
+            '#'
+        }
+    })
+}
+
+fn day22(rng: &mut ChaCha8Rng) -> String {
+    type Vector3 = euclid::Vector3D<isize, ()>;
+    let count = rng.gen_range(10..40);
+    let mut filled = HashSet::new();
+    let mut bricks = Vec::new();
+    while bricks.len() < count {
+        let a = Vector3::new(
+            rng.gen_range(0..4),
+            rng.gen_range(0..4),
+            rng.gen_range(1..=2 * count as isize),
+        );
+        let axis = [
+            Vector3::new(1, 0, 0),
+            Vector3::new(0, 1, 0),
+            Vector3::new(0, 0, 1),
+        ]
+        .choose(rng)
+        .copied()
+        .unwrap();
+        let cubes = (0..rng.gen_range(1..=3))
+            .map(|i| a + axis * i)
+            .collect_vec();
+        if cubes
+            .iter()
+            .any(|c| c.x > 3 || c.y > 3 || filled.contains(c))
+        {
+            continue;
+        }
+        filled.extend(cubes.iter().copied());
+        let b = cubes.last().unwrap();
+        bricks.push(format!("{},{},{}~{},{},{}", a.x, a.y, a.z, b.x, b.y, b.z));
+    }
+    lines(bricks)
+}
+
+fn day23(rng: &mut ChaCha8Rng) -> String {
+    // A maze of cells on odd tiles carved as a tree from the top left cell,
+    // with slopes pointing away from the root on the tree's passages so
+    // part 1 can always get through, plus a few plain passages that add
+    // loops.
+    let (cols, rows) = (rng.gen_range(3..6), rng.gen_range(3..6));
+    let in_maze = |v: Vector| (0..cols).contains(&v.x) && (0..rows).contains(&v.y);
+    let mut tiles = HashMap::new();
+    let mut visited = HashSet::from([Vector::zero()]);
+    let mut stack = vec![Vector::zero()];
+    while let Some(&cell) = stack.last() {
+        let next = Direction::all()
+            .filter(|d| {
+                let n = cell + d.to_vector();
+                in_maze(n) && !visited.contains(&n)
+            })
+            .collect_vec();
+        match next.choose(rng) {
+            Some(d) => {
+                let slope = match d {
+                    Direction::Up => '^',
+                    Direction::Down => 'v',
+                    Direction::Left => '<',
+                    _ => '>',
+                };
+                tiles.insert(cell * 2 + d.to_vector() + Vector::new(1, 1), slope);
+                let n = cell + d.to_vector();
+                visited.insert(n);
+                stack.push(n);
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    let mut loops = rng.gen_range(1..4);
+    while loops > 0 {
+        let cell = Vector::new(rng.gen_range(0..cols), rng.gen_range(0..rows));
+        let d = Direction::all()
+            .collect_vec()
+            .choose(rng)
+            .unwrap()
+            .to_vector();
+        if in_maze(cell + d) {
+            let gap = cell * 2 + d + Vector::new(1, 1);
+            if let std::collections::hash_map::Entry::Vacant(e) = tiles.entry(gap) {
+                e.insert('.');
+                loops -= 1;
+            }
+        }
+    }
+    let (width, height) = (2 * cols + 1, 2 * rows + 1);
+    tiles.insert(Vector::new(1, 0), '.');
+    tiles.insert(Vector::new(width - 2, height - 1), '.');
+    grid(width as usize, height as usize, |v| {
+        if v.x % 2 == 1 && v.y % 2 == 1 {
+            '.'
+        } else {
+            tiles.get(&v).copied().unwrap_or('#')
+        }
+    })
+}
+
+fn day24(rng: &mut ChaCha8Rng) -> String {
+    // Hail placed so a rock thrown from `rock` at `speed` hits each of them
+    // at a whole time, at the scale of the real inputs so paths cross inside
+    // part 1's test area.
+    let rock = [(); 3].map(|_| rng.gen_range(200_000_000_000_000i64..300_000_000_000_000));
+    let speed = [(); 3].map(|_| rng.gen_range(-200i64..=200));
+    let mut times = HashSet::new();
+    lines((0..rng.gen_range(5..=8)).map(|_| {
+        let time = loop {
+            let t = rng.gen_range(100_000_000_000i64..1_000_000_000_000);
+            if times.insert(t) {
+                break t;
+            }
+        };
+        let hail_speed = speed.map(|s| loop {
+            let v = rng.gen_range(-200i64..=200);
+            if v != 0 && v != s {
+                break v;
+            }
+        });
+        let pos = [0, 1, 2].map(|i| rock[i] + (speed[i] - hail_speed[i]) * time);
+        let [x, y, z] = pos;
+        let [vx, vy, vz] = hail_speed;
+        format!("{x}, {y}, {z} @ {vx}, {vy}, {vz}")
+    }))
+}
+
+fn day25(rng: &mut ChaCha8Rng) -> String {
+    // Two groups each wired as a ring that also links every second
+    // component, so nothing short of four wires splits them, joined by
+    // three wires.
+    let sizes = [rng.gen_range(8..16), rng.gen_range(8..16)];
+    let mut ids = (0..sizes[0] + sizes[1]).collect_vec();
+    ids.shuffle(rng);
+    let (a, b) = ids.split_at(sizes[0]);
+    let mut wires = HashSet::new();
+    for group in [a, b] {
+        let len = group.len();
+        for i in 0..len {
+            wires.insert(crate::minmax(group[i], group[(i + 1) % len]));
+            wires.insert(crate::minmax(group[i], group[(i + 2) % len]));
+        }
+        for _ in 0..len / 2 {
+            let (x, y) = (group.choose(rng).unwrap(), group.choose(rng).unwrap());
+            if x != y {
+                wires.insert(crate::minmax(*x, *y));
+            }
+        }
+    }
+    let mut cut_a = a.to_vec();
+    let mut cut_b = b.to_vec();
+    cut_a.shuffle(rng);
+    cut_b.shuffle(rng);
+    wires.extend(
+        cut_a
+            .iter()
+            .zip(&cut_b)
+            .take(3)
+            .map(|(x, y)| crate::minmax(*x, *y)),
+    );
+
+    let mut taken = HashSet::new();
+    let names = ids
+        .iter()
+        .map(|_| name(rng, 3, LOWERCASE, &mut taken))
+        .collect_vec();
+    let mut out = wires
+        .into_iter()
+        .sorted()
+        .map(|(x, y)| if rng.gen_bool(0.5) { (x, y) } else { (y, x) })
+        .into_group_map()
+        .into_iter()
+        .sorted()
+        .map(|(x, ys)| format!("{}: {}", names[x], ys.iter().map(|y| &names[*y]).join(" ")))
+        .collect_vec();
+    out.shuffle(rng);
+    lines(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution;
+
+    #[test]
+    fn solvable() {
+        for entry in solution::DAYS.iter() {
+            for seed in 0..3 {
+                let input = super::input(entry.day, seed).unwrap();
+                let parsed = entry
+                    .parse(&input)
+                    .unwrap_or_else(|e| panic!("day {} seed {seed}: {e}\n{input}", entry.day));
+                for part in 1..=entry.parts {
+                    entry.solve(&parsed, part).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reproducible() {
+        assert_eq!(super::input(19, 7), super::input(19, 7));
+        assert_ne!(super::input(19, 7), super::input(19, 8));
+        assert_eq!(super::input(26, 0), None);
+    }
+
+    // A seed must give the same input on every machine and every build, a
+    // change of generator algorithm shows up here.
+    #[test]
+    fn stable() {
+        assert_eq!(
+            super::input(6, 0).unwrap(),
+            "Time:       75   55   60   27\nDistance:  466  529   55  106\n"
+        );
+    }
+}
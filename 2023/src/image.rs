@@ -0,0 +1,130 @@
+use std::{io, path::Path};
+
+pub use bmp::{Image, Pixel};
+
+use crate::Map;
+
+pub const BLACK: Pixel = Pixel { r: 0, g: 0, b: 0 };
+pub const GREY: Pixel = Pixel {
+    r: 96,
+    g: 96,
+    b: 96,
+};
+pub const WHITE: Pixel = Pixel {
+    r: 255,
+    g: 255,
+    b: 255,
+};
+pub const RED: Pixel = Pixel {
+    r: 220,
+    g: 40,
+    b: 40,
+};
+pub const GREEN: Pixel = Pixel {
+    r: 60,
+    g: 180,
+    b: 75,
+};
+pub const YELLOW: Pixel = Pixel {
+    r: 255,
+    g: 210,
+    b: 40,
+};
+
+impl<T> Map<T> {
+    /// One pixel per node, coloured by `palette`.
+    pub fn to_bmp(&self, mut palette: impl FnMut(&T) -> Pixel) -> Image {
+        let mut image = Image::new(self.cols as u32, self.lines() as u32);
+        for (i, node) in self.nodes.iter().enumerate() {
+            let v = self.converter().to_vector(i);
+            image.set_pixel(v.x as u32, v.y as u32, palette(node));
+        }
+        image
+    }
+}
+
+/// Each pixel of `image` as a `factor` wide square.
+pub fn scale(image: &Image, factor: u32) -> Image {
+    let factor = factor.max(1);
+    let mut scaled = Image::new(image.get_width() * factor, image.get_height() * factor);
+    for y in 0..scaled.get_height() {
+        for x in 0..scaled.get_width() {
+            scaled.set_pixel(x, y, image.get_pixel(x / factor, y / factor));
+        }
+    }
+    scaled
+}
+
+/// Frames of a simulation, keeps one in `stride` so long runs stay small.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    stride: usize,
+    /// Frames to drop before keeping one.
+    skip: usize,
+    frames: Vec<Image>,
+}
+
+impl Recorder {
+    pub fn new(stride: usize) -> Self {
+        Self {
+            stride: stride.max(1),
+            skip: 0,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Offers a frame, the first one is always kept.
+    pub fn record<T>(&mut self, map: &Map<T>, palette: impl FnMut(&T) -> Pixel) {
+        if self.skip == 0 {
+            self.frames.push(map.to_bmp(palette));
+            self.skip = self.stride;
+        }
+        self.skip -= 1;
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    /// Writes the frames to `dir` as `frame0.bmp`, `frame1.bmp`... padded so
+    /// they sort, returns how many.
+    pub fn save(&self, dir: &Path, factor: u32) -> io::Result<usize> {
+        std::fs::create_dir_all(dir)?;
+        let width = self.frames.len().to_string().len();
+        for (i, frame) in self.frames.iter().enumerate() {
+            scale(frame, factor).save(dir.join(format!("frame{i:0width$}.bmp")))?;
+        }
+        Ok(self.frames.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Recorder, BLACK, WHITE};
+    use crate::Map;
+
+    #[test]
+    fn to_bmp() {
+        let map = Map::new(vec![true, false, false, true, true, false], 3);
+        let image = map.to_bmp(|b| if *b { WHITE } else { BLACK });
+        assert_eq!((image.get_width(), image.get_height()), (3, 2));
+        assert_eq!(image.get_pixel(0, 0), WHITE);
+        assert_eq!(image.get_pixel(2, 0), BLACK);
+        assert_eq!(image.get_pixel(1, 1), WHITE);
+
+        let image = super::scale(&image, 2);
+        assert_eq!((image.get_width(), image.get_height()), (6, 4));
+        assert_eq!(image.get_pixel(3, 3), WHITE);
+        assert_eq!(image.get_pixel(5, 1), BLACK);
+    }
+
+    #[test]
+    fn recorder() {
+        let mut recorder = Recorder::new(3);
+        for i in 0..7 {
+            let map = Map::new(vec![i], 1);
+            recorder.record(&map, |_| WHITE);
+        }
+        assert_eq!(recorder.frames().len(), 3);
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable that overrides the inputs directory.
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing {
+        day: u8,
+        path: PathBuf,
+    },
+    Io {
+        day: u8,
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing { day, path } => {
+                write!(f, "input for day {day} missing at {}", path.display())
+            }
+            Self::Io { day, path, error } => {
+                write!(
+                    f,
+                    "can't read input for day {day} at {}: {error}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Returns the inputs directory, `$AOC_INPUTS` or `src/input` in the crate.
+pub fn dir() -> PathBuf {
+    std::env::var_os(INPUTS_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            [env!("CARGO_MANIFEST_DIR"), "src", "input"]
+                .iter()
+                .collect()
+        })
+}
+
+pub fn path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{day}.txt"))
+}
+
+/// What [`normalize`] changed in an input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Normalized {
+    pub bom: bool,
+    /// `\r\n` and lone `\r` line endings.
+    pub carriage_returns: usize,
+    /// Lines with trailing whitespace.
+    pub trailing_whitespace: usize,
+    pub blank_lines_at_end: usize,
+    pub missing_final_newline: bool,
+}
+
+impl Normalized {
+    pub fn is_unchanged(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Display for Normalized {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let lines = |n: usize| {
+            if n == 1 {
+                "1 line".to_string()
+            } else {
+                format!("{n} lines")
+            }
+        };
+        let mut changes = Vec::new();
+        if self.bom {
+            changes.push("stripped the byte order mark".to_string());
+        }
+        if self.carriage_returns > 0 {
+            changes.push(format!(
+                "fixed the endings of {}",
+                lines(self.carriage_returns)
+            ));
+        }
+        if self.trailing_whitespace > 0 {
+            changes.push(format!(
+                "trimmed trailing whitespace on {}",
+                lines(self.trailing_whitespace)
+            ));
+        }
+        if self.blank_lines_at_end > 0 {
+            changes.push(format!(
+                "removed {} at the end",
+                lines(self.blank_lines_at_end)
+            ));
+        }
+        if self.missing_final_newline {
+            changes.push("added the final newline".to_string());
+        }
+        if changes.is_empty() {
+            return write!(f, "unchanged");
+        }
+        write!(f, "{}", changes.join(", "))
+    }
+}
+
+/// Makes inputs saved on other systems or pasted from a browser look like
+/// the downloaded ones: LF line endings, no byte order mark, no trailing
+/// whitespace and a single final newline.
+pub fn normalize(input: &str) -> (String, Normalized) {
+    let mut changes = Normalized::default();
+    let input = match input.strip_prefix('\u{feff}') {
+        Some(rest) => {
+            changes.bom = true;
+            rest
+        }
+        None => input,
+    };
+    changes.carriage_returns = input.matches('\r').count();
+    let input = input.replace("\r\n", "\n").replace('\r', "\n");
+
+    let mut lines = input
+        .split('\n')
+        .map(|l| {
+            let trimmed = l.trim_end();
+            if trimmed.len() != l.len() {
+                changes.trailing_whitespace += 1;
+            }
+            trimmed
+        })
+        .collect::<Vec<_>>();
+    // Splitting a properly terminated input leaves an empty last line.
+    match lines.last() {
+        Some(&"") => {
+            lines.pop();
+        }
+        _ => changes.missing_final_newline = !input.is_empty(),
+    }
+    while lines.last() == Some(&"") {
+        lines.pop();
+        changes.blank_lines_at_end += 1;
+    }
+    if lines.is_empty() {
+        changes.missing_final_newline = false;
+        return (String::new(), changes);
+    }
+    (lines.join("\n") + "\n", changes)
+}
+
+/// Reads and normalizes the input at `path`.
+pub fn read_path(day: u8, path: &Path) -> Result<(String, Normalized), InputError> {
+    std::fs::read_to_string(path)
+        .map(|input| normalize(&input))
+        .map_err(|error| {
+            let path = path.to_path_buf();
+            match error.kind() {
+                std::io::ErrorKind::NotFound => InputError::Missing { day, path },
+                _ => InputError::Io { day, path, error },
+            }
+        })
+}
+
+/// Same as [`read_path`], logs the changes.
+pub fn load_path(day: u8, path: &Path) -> Result<String, InputError> {
+    let (input, changes) = read_path(day, path)?;
+    if !changes.is_unchanged() {
+        log::info!("day {day} input: {changes}");
+    }
+    Ok(input)
+}
+
+pub fn load_from(dir: &Path, day: u8) -> Result<String, InputError> {
+    load_path(day, &path(dir, day))
+}
+
+pub fn load(day: u8) -> Result<String, InputError> {
+    load_from(&dir(), day)
+}
+
+/// Runs `f` with the input for `day`, tests are skipped when the input is
+/// missing so a fresh checkout still passes.
+#[cfg(test)]
+pub(crate) fn with_input(day: u8, f: impl FnOnce(&str)) {
+    match load(day) {
+        Ok(input) => f(&input),
+        Err(e) => println!("skipping: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize, Normalized};
+
+    #[test]
+    fn unchanged() {
+        let (input, changes) = normalize("ab\n\ncd\n");
+        assert_eq!(input, "ab\n\ncd\n");
+        assert!(changes.is_unchanged());
+        assert_eq!(normalize(""), (String::new(), Normalized::default()));
+    }
+
+    #[test]
+    fn normalized() {
+        let (input, changes) = normalize("\u{feff}ab \r\n\t\r\ncd\rx y\t\n\n \n");
+        assert_eq!(input, "ab\n\ncd\nx y\n");
+        assert_eq!(
+            changes,
+            Normalized {
+                bom: true,
+                carriage_returns: 3,
+                trailing_whitespace: 4,
+                blank_lines_at_end: 2,
+                missing_final_newline: false,
+            }
+        );
+        assert_eq!(
+            changes.to_string(),
+            "stripped the byte order mark, fixed the endings of 3 lines, \
+             trimmed trailing whitespace on 4 lines, removed 2 lines at the end"
+        );
+
+        let (input, changes) = normalize("ab\ncd");
+        assert_eq!(input, "ab\ncd\n");
+        assert!(changes.missing_final_newline);
+    }
+}
@@ -0,0 +1,195 @@
+use std::ops::Range;
+
+use num::PrimInt;
+
+/// Set of integers kept as sorted, disjoint and non adjacent half open
+/// ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    fn normalize(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|r| r.start < r.end);
+        ranges.sort_by_key(|r| r.start);
+        let mut merged: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for r in ranges {
+            match merged.last_mut() {
+                Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+                _ => merged.push(r),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |len, r| len + (r.end - r.start))
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    pub fn contains(&self, v: T) -> bool {
+        self.ranges.iter().any(|r| r.contains(&v))
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        let ranges = std::mem::take(&mut self.ranges);
+        *self = ranges.into_iter().chain([range]).collect();
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.ranges.iter().chain(&other.ranges).cloned().collect()
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (mut i, mut j) = (0, 0);
+        let mut ranges = Vec::new();
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Values in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for r in &self.ranges {
+            while other.ranges.get(j).is_some_and(|o| o.end <= r.start) {
+                j += 1;
+            }
+            let mut start = r.start;
+            for o in other.ranges[j..].iter().take_while(|o| o.start < r.end) {
+                if start < o.start {
+                    ranges.push(start..o.start);
+                }
+                start = start.max(o.end);
+            }
+            if start < r.end {
+                ranges.push(start..r.end);
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).is_empty()
+    }
+
+    /// Values below `at` and values from `at` on.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let mut below = Vec::new();
+        let mut above = Vec::new();
+        for r in &self.ranges {
+            if r.end <= at {
+                below.push(r.clone());
+            } else if r.start >= at {
+                above.push(r.clone());
+            } else {
+                below.push(r.start..at);
+                above.push(at..r.end);
+            }
+        }
+        (Self { ranges: below }, Self { ranges: above })
+    }
+
+    /// Moves the values in each piece's range so the range starts at the
+    /// piece's target, values outside every piece stay. The first piece
+    /// containing a value wins.
+    pub fn offset_map(&self, pieces: impl IntoIterator<Item = (Range<T>, T)>) -> Self {
+        let mut rest = self.clone();
+        let mut moved = Vec::new();
+        for (src, dst) in pieces {
+            let hit = rest.intersection(&src.clone().into());
+            rest = rest.difference(&hit);
+            moved.extend(
+                hit.ranges
+                    .into_iter()
+                    .map(|r| r.start - src.start + dst..r.end - src.start + dst),
+            );
+        }
+        rest.ranges.into_iter().chain(moved).collect()
+    }
+}
+
+impl<T: PrimInt> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::normalize(vec![range])
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntervalSet;
+
+    fn set(ranges: &[(i32, i32)]) -> IntervalSet<i32> {
+        ranges.iter().map(|&(a, b)| a..b).collect()
+    }
+
+    #[test]
+    fn normalize() {
+        let s = set(&[(5, 8), (0, 2), (2, 3), (7, 10), (4, 4)]);
+        assert_eq!(s.ranges(), [0..3, 5..10]);
+        assert_eq!((s.len(), s.min()), (8, Some(0)));
+        assert!(s.contains(9) && !s.contains(3));
+    }
+
+    #[test]
+    fn operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (28, 40)]);
+        assert_eq!(a.union(&b), set(&[(0, 40)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25), (28, 30)]));
+        assert_eq!(a.difference(&b), set(&[(0, 5), (25, 28)]));
+        assert_eq!(b.difference(&a), set(&[(10, 20), (30, 40)]));
+        assert!(a.is_disjoint(&set(&[(10, 20)])));
+        assert_eq!(a.split_at(5), (set(&[(0, 5)]), set(&[(5, 10), (20, 30)])));
+        assert_eq!(a.split_at(15), (set(&[(0, 10)]), set(&[(20, 30)])));
+    }
+
+    #[test]
+    fn offset_map() {
+        let s = set(&[(0, 10)]);
+        let mapped = s.offset_map([(2..4, 100), (3..6, 50), (8..20, -4)]);
+        assert_eq!(mapped.ranges(), [-4..-2, 0..2, 6..8, 51..53, 100..102]);
+    }
+}
@@ -1,34 +1,55 @@
-#![cfg(test)]
-
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-type Vector = euclid::Vector2D<isize, ()>;
-
-enum MapCoordinate {
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cycle;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod generate;
+pub mod image;
+pub mod input;
+pub mod interval;
+pub mod parse;
+pub mod report;
+pub mod search;
+pub mod solution;
+pub mod terminal;
+
+#[cfg(test)]
+mod reference;
+
+use std::fmt::Display;
+
+pub use answer::Answer;
+pub use interval::IntervalSet;
+pub use parse::ParseError;
+pub use solution::Solution;
+
+pub type Vector = euclid::Vector2D<isize, ()>;
+
+pub enum MapCoordinate {
     Vector(Vector),
     Idx(usize),
 }
@@ -46,15 +67,14 @@ impl From<usize> for MapCoordinate {
 }
 
 impl MapCoordinate {
-    fn into_idx(self, conv: MapCoordinateConverter) -> Option<usize> {
+    pub fn into_idx(self, conv: MapCoordinateConverter) -> Option<usize> {
         match self {
             Self::Idx(v) => Some(v),
             Self::Vector(v) => conv.to_idx(v),
         }
     }
 
-    #[allow(dead_code)]
-    fn into_vector(self, conv: MapCoordinateConverter) -> Vector {
+    pub fn into_vector(self, conv: MapCoordinateConverter) -> Vector {
         match self {
             MapCoordinate::Vector(v) => v,
             MapCoordinate::Idx(i) => conv.to_vector(i),
@@ -63,13 +83,13 @@ impl MapCoordinate {
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
-struct MapCoordinateConverter {
+pub struct MapCoordinateConverter {
     cols: usize,
     lines: usize,
 }
 
 impl MapCoordinateConverter {
-    fn to_idx(&self, Vector { x, y, .. }: Vector) -> Option<usize> {
+    pub fn to_idx(&self, Vector { x, y, .. }: Vector) -> Option<usize> {
         let x = usize::try_from(x).ok()?;
         if x >= self.cols {
             return None;
@@ -81,22 +101,26 @@ impl MapCoordinateConverter {
         Some(y * self.cols + x)
     }
 
-    fn to_vector(&self, idx: usize) -> Vector {
+    pub fn to_vector(&self, idx: usize) -> Vector {
         let x = isize::try_from(idx % self.cols).unwrap();
         let y = isize::try_from(idx / self.cols).unwrap();
         Vector::new(x, y)
     }
 
+    /// In bounds neighbours of `v` towards `directions`, with their index.
+    pub fn neighbours(
+        self,
+        v: Vector,
+        directions: impl Iterator<Item = Direction>,
+    ) -> impl Iterator<Item = (Direction, usize)> {
+        directions.filter_map(move |d| Some((d, self.to_idx(v + d.to_vector())?)))
+    }
+
     /// Returns the wrapped vector and a vector with the number of wraps in the
     /// wrap direction.
-    #[allow(dead_code)]
-    fn wrap_vector(&self, v: Vector) -> (Vector, Vector) {
+    pub fn wrap_vector(&self, v: Vector) -> (Vector, Vector) {
         fn wrap(coord: isize, max: isize) -> (isize, isize) {
-            let sig = isize::signum(coord);
-            let m = (sig - 1) / 2;
-            let a = coord.rem_euclid(max);
-            let b = coord / max + m;
-            (a, b)
+            (coord.rem_euclid(max), coord.div_euclid(max))
         }
         let (x, wx) = wrap(v.x, self.cols as isize);
         let (y, wy) = wrap(v.y, self.lines as isize);
@@ -104,73 +128,362 @@ impl MapCoordinateConverter {
     }
 }
 
-#[derive(Debug)]
-struct Map<T> {
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Map<T> {
     nodes: Vec<T>,
     cols: usize,
 }
 
 impl<T> Map<T> {
-    fn new(nodes: Vec<T>, cols: usize) -> Self {
+    pub fn new(nodes: Vec<T>, cols: usize) -> Self {
         Self { nodes, cols }
     }
 
-    fn converter(&self) -> MapCoordinateConverter {
+    /// Builds a map calling `f` with the coordinate of every node.
+    pub fn from_fn(cols: usize, lines: usize, mut f: impl FnMut(Vector) -> T) -> Self {
+        let nodes = (0..lines)
+            .flat_map(|y| (0..cols).map(move |x| Vector::new(x as isize, y as isize)))
+            .map(&mut f)
+            .collect();
+        Self::new(nodes, cols)
+    }
+
+    /// Parses a grid with one node per char, every line must have the same
+    /// length.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut lines = parse::lines(day, input);
+        let map = Self::parse_lines(&mut lines, f)?;
+        match lines.next() {
+            Some(l) => Err(l.error(l.text, "expected end of map")),
+            None => Ok(map),
+        }
+    }
+
+    /// Parses the grid at the start of `lines`, up to and including the next
+    /// empty line.
+    pub fn parse_lines(
+        lines: &mut parse::Lines,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let first = lines.expect("map")?;
+        let mut nodes = first.chars(&mut f).collect::<Result<Vec<_>, _>>()?;
+        let cols = nodes.len();
+        for line in lines.take_while(|l| !l.text.is_empty()) {
+            let start = nodes.len();
+            for node in line.chars(&mut f) {
+                nodes.push(node?);
+            }
+            if nodes.len() - start != cols {
+                return Err(line.error(line.text, format!("expected {cols} columns")));
+            }
+        }
+        if cols == 0 {
+            return Err(first.error(first.text, "empty map"));
+        }
+        Ok(Self::new(nodes, cols))
+    }
+
+    pub fn converter(&self) -> MapCoordinateConverter {
         MapCoordinateConverter {
             cols: self.cols,
             lines: self.lines(),
         }
     }
 
-    fn at<C: Into<MapCoordinate>>(&self, t: C) -> Option<&T> {
+    pub fn at<C: Into<MapCoordinate>>(&self, t: C) -> Option<&T> {
         t.into()
             .into_idx(self.converter())
             .and_then(|i| self.nodes.get(i))
     }
 
-    fn at_mut<C: Into<MapCoordinate>>(&mut self, t: C) -> Option<&mut T> {
+    pub fn at_mut<C: Into<MapCoordinate>>(&mut self, t: C) -> Option<&mut T> {
         t.into()
             .into_idx(self.converter())
             .and_then(|i| self.nodes.get_mut(i))
     }
 
-    fn lines(&self) -> usize {
+    pub fn nodes(&self) -> &[T] {
+        &self.nodes[..]
+    }
+
+    pub fn nodes_mut(&mut self) -> &mut [T] {
+        &mut self.nodes[..]
+    }
+
+    pub fn into_nodes(self) -> Vec<T> {
+        self.nodes
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn lines(&self) -> usize {
         self.nodes.len() / self.cols
     }
 
-    fn bounds(&self) -> Vector {
+    pub fn bounds(&self) -> Vector {
         Vector::new(self.cols as isize, self.lines() as isize)
     }
+
+    /// Up, down, left and right neighbours of `c` inside the map.
+    pub fn neighbours<C: Into<MapCoordinate>>(
+        &self,
+        c: C,
+    ) -> impl Iterator<Item = (Direction, usize, &T)> {
+        self.neighbours_towards(c, Direction::all())
+    }
+
+    pub fn diagonal_neighbours<C: Into<MapCoordinate>>(
+        &self,
+        c: C,
+    ) -> impl Iterator<Item = (Direction, usize, &T)> {
+        self.neighbours_towards(c, Direction::diagonals())
+    }
+
+    /// Orthogonal and diagonal neighbours.
+    pub fn all_neighbours<C: Into<MapCoordinate>>(
+        &self,
+        c: C,
+    ) -> impl Iterator<Item = (Direction, usize, &T)> {
+        self.neighbours_towards(c, Direction::all_with_diagonals())
+    }
+
+    fn neighbours_towards<C: Into<MapCoordinate>>(
+        &self,
+        c: C,
+        directions: impl Iterator<Item = Direction>,
+    ) -> impl Iterator<Item = (Direction, usize, &T)> {
+        let conv = self.converter();
+        conv.neighbours(c.into().into_vector(conv), directions)
+            .map(|(d, i)| (d, i, &self.nodes[i]))
+    }
+
+    /// View of the map repeated in every direction.
+    pub fn tiled(&self) -> TiledMap<'_, T> {
+        TiledMap { map: self }
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        self.nodes.chunks_exact(self.cols).nth(y)
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        self.nodes.chunks_exact(self.cols)
+    }
+
+    /// Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.cols, "column {x} out of bounds");
+        self.nodes[x..].iter().step_by(self.cols)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.cols).map(|x| self.column(x))
+    }
+
+    /// Renders the map a char per node, a line per row.
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut s = String::with_capacity(self.nodes.len() + self.lines());
+        for row in self.rows() {
+            s.extend(row.iter().map(&mut f));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+impl<T: Clone> Map<T> {
+    fn remap(&self, cols: usize, lines: usize, f: impl Fn(isize, isize) -> Vector) -> Self {
+        Self::from_fn(cols, lines, |v| self.at(f(v.x, v.y)).unwrap().clone())
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.lines(), self.cols, |x, y| Vector::new(y, x))
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_right(&self) -> Self {
+        let lines = self.lines() as isize;
+        self.remap(self.lines(), self.cols, |x, y| {
+            Vector::new(y, lines - 1 - x)
+        })
+    }
+
+    /// Rotates a quarter turn counter clockwise.
+    pub fn rotate_left(&self) -> Self {
+        let cols = self.cols as isize;
+        self.remap(self.lines(), self.cols, |x, y| Vector::new(cols - 1 - y, x))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let cols = self.cols as isize;
+        self.remap(self.cols, self.lines(), |x, y| Vector::new(cols - 1 - x, y))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let lines = self.lines() as isize;
+        self.remap(self.cols, self.lines(), |x, y| {
+            Vector::new(x, lines - 1 - y)
+        })
+    }
+}
+
+/// A [`Map`] repeated infinitely in every direction, any coordinate resolves
+/// to a node of the map in some tile.
+#[derive(Debug)]
+pub struct TiledMap<'a, T> {
+    map: &'a Map<T>,
+}
+
+impl<T> Clone for TiledMap<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TiledMap<'_, T> {}
+
+impl<'a, T> TiledMap<'a, T> {
+    pub fn map(&self) -> &'a Map<T> {
+        self.map
+    }
+
+    /// Returns the index in the map of the node at `v` and the coordinate of
+    /// its tile, the map itself is tile `(0, 0)`.
+    pub fn resolve(&self, v: Vector) -> (usize, Vector) {
+        let conv = self.map.converter();
+        let (v, tile) = conv.wrap_vector(v);
+        (conv.to_idx(v).unwrap(), tile)
+    }
+
+    pub fn at(&self, v: Vector) -> &'a T {
+        &self.map.nodes[self.resolve(v).0]
+    }
+
+    /// Up, down, left and right neighbours of `v`, which always exist.
+    pub fn neighbours(&self, v: Vector) -> impl Iterator<Item = (Direction, Vector, &'a T)> {
+        let tiled = *self;
+        Direction::all().map(move |d| {
+            let n = v + d.to_vector();
+            (d, n, tiled.at(n))
+        })
+    }
+}
+
+impl<T: Display> Display for Map<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for node in row {
+                write!(f, "{node}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Copy, Clone)]
-enum Direction {
+pub enum Direction {
     Up,
     Down,
     Left,
     Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
 }
 
 impl Direction {
-    fn all() -> impl Iterator<Item = Direction> {
+    /// The orthogonal directions.
+    pub fn all() -> impl Iterator<Item = Direction> {
         [Self::Up, Self::Down, Self::Left, Self::Right].into_iter()
     }
 
-    fn to_vector(&self) -> Vector {
+    pub fn diagonals() -> impl Iterator<Item = Direction> {
+        [Self::UpLeft, Self::UpRight, Self::DownLeft, Self::DownRight].into_iter()
+    }
+
+    pub fn all_with_diagonals() -> impl Iterator<Item = Direction> {
+        Self::all().chain(Self::diagonals())
+    }
+
+    /// Direction of a unit vector, orthogonal or diagonal.
+    pub fn from_vector(v: Vector) -> Option<Direction> {
+        Self::all_with_diagonals().find(|d| d.to_vector() == v)
+    }
+
+    pub fn to_vector(&self) -> Vector {
         match self {
             Direction::Up => Vector::new(0, -1),
             Direction::Down => Vector::new(0, 1),
             Direction::Left => Vector::new(-1, 0),
             Direction::Right => Vector::new(1, 0),
+            Direction::UpLeft => Vector::new(-1, -1),
+            Direction::UpRight => Vector::new(1, -1),
+            Direction::DownLeft => Vector::new(-1, 1),
+            Direction::DownRight => Vector::new(1, 1),
         }
     }
 
-    fn opposite(&self) -> Direction {
+    pub fn opposite(&self) -> Direction {
         match self {
             Direction::Up => Direction::Down,
             Direction::Down => Direction::Up,
             Direction::Left => Direction::Right,
             Direction::Right => Direction::Left,
+            Direction::UpLeft => Direction::DownRight,
+            Direction::UpRight => Direction::DownLeft,
+            Direction::DownLeft => Direction::UpRight,
+            Direction::DownRight => Direction::UpLeft,
+        }
+    }
+
+    /// Quarter turn clockwise.
+    pub fn turn_right(&self) -> Direction {
+        let v = self.to_vector();
+        Self::from_vector(Vector::new(-v.y, v.x)).unwrap()
+    }
+
+    /// Quarter turn counter clockwise.
+    pub fn turn_left(&self) -> Direction {
+        let v = self.to_vector();
+        Self::from_vector(Vector::new(v.y, -v.x)).unwrap()
+    }
+}
+
+/// Accepts `URDL`, `NESW`, `^>v<` and `0123` with 0 to the right, 1 down,
+/// 2 left and 3 up.
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Ok(match c {
+            'U' | 'N' | '^' | '3' => Self::Up,
+            'R' | 'E' | '>' | '0' => Self::Right,
+            'D' | 'S' | 'v' | '1' => Self::Down,
+            'L' | 'W' | '<' | '2' => Self::Left,
+            c => return Err(c),
+        })
+    }
+}
+
+impl std::str::FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from(c).map_err(|c| format!("unknown direction {c:?}")),
+            _ => Err(format!("unknown direction {s:?}")),
         }
     }
 }
@@ -181,3 +494,108 @@ fn minmax<T: std::cmp::Ord>(a: T, b: T) -> (T, T) {
         std::cmp::Ordering::Greater => (b, a),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Map, Vector};
+
+    const GRID: &str = "\
+abc
+def
+";
+
+    fn grid() -> Map<char> {
+        Map::parse(0, GRID, Some).unwrap()
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let map = grid();
+        assert_eq!(map.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(map.row(2), None);
+        assert_eq!(map.column(2).collect::<String>(), "cf");
+        let columns = map
+            .columns()
+            .map(|c| c.collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, ["ad", "be", "cf"]);
+        assert_eq!(map.to_string(), GRID);
+        assert_eq!(map.render(|c| c.to_ascii_uppercase()), "ABC\nDEF\n");
+    }
+
+    #[test]
+    fn transforms() {
+        let map = grid();
+        assert_eq!(map.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(map.rotate_right().to_string(), "da\neb\nfc\n");
+        assert_eq!(map.rotate_left().to_string(), "cf\nbe\nad\n");
+        assert_eq!(map.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(map.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(
+            map.rotate_right().rotate_right().to_string(),
+            map.flip_horizontal().flip_vertical().to_string()
+        );
+        assert_eq!(map.at(Vector::new(2, 1)), Some(&'f'));
+    }
+
+    #[test]
+    fn neighbours() {
+        let map = grid();
+        let chars = |n: &mut dyn Iterator<Item = (Direction, usize, &char)>| {
+            n.map(|(_, _, c)| *c).collect::<String>()
+        };
+        assert_eq!(chars(&mut map.neighbours(Vector::new(0, 0))), "db");
+        assert_eq!(chars(&mut map.diagonal_neighbours(1)), "df");
+        assert_eq!(chars(&mut map.all_neighbours(4)), "bdfac");
+        let (d, i, c) = map.neighbours(5).next().unwrap();
+        assert_eq!((d, i, *c), (Direction::Up, 2, 'c'));
+    }
+
+    #[test]
+    fn tiled() {
+        let map = grid();
+        let tiled = map.tiled();
+        assert_eq!(tiled.resolve(Vector::new(1, 1)), (4, Vector::new(0, 0)));
+        assert_eq!(tiled.resolve(Vector::new(-1, 2)), (2, Vector::new(-1, 1)));
+        assert_eq!(tiled.resolve(Vector::new(-3, -2)), (0, Vector::new(-1, -1)));
+        assert_eq!(tiled.resolve(Vector::new(-4, 5)), (5, Vector::new(-2, 2)));
+        assert_eq!(*tiled.at(Vector::new(6, -1)), 'd');
+        let around = tiled
+            .neighbours(Vector::new(0, 0))
+            .map(|(_, _, c)| *c)
+            .collect::<String>();
+        assert_eq!(around, "ddcb");
+    }
+
+    #[test]
+    fn directions() {
+        for d in Direction::all_with_diagonals() {
+            assert_eq!(Direction::from_vector(d.to_vector()), Some(d));
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+        }
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::UpLeft.turn_left(), Direction::DownLeft);
+        assert_eq!(Direction::from_vector(Vector::new(2, 0)), None);
+        let parsed = ["U", "E", "v", "2"].map(|s| s.parse::<Direction>().unwrap());
+        assert_eq!(
+            parsed,
+            [
+                Direction::Up,
+                Direction::Right,
+                Direction::Down,
+                Direction::Left
+            ]
+        );
+        assert_eq!(Direction::try_from('x'), Err('x'));
+        assert!("UR".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let e = Map::parse(0, "ab\nc\n", Some).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (2, "expected 2 columns"));
+        let e = Map::parse(0, "ab\n\ncd\n", Some).unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (3, "expected end of map"));
+    }
+}
@@ -0,0 +1,424 @@
+use std::path::{Path, PathBuf};
+
+use aoc2023::{
+    answers::Verdict,
+    report::{PartReport, Status},
+    *,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    /// Directory with the `dayN.txt` inputs, defaults to `$AOC_INPUTS` or
+    /// `src/input`.
+    #[arg(long, global = true)]
+    inputs: Option<PathBuf>,
+    /// Logs what the solvers are doing, twice for every step. `RUST_LOG`
+    /// picks days instead, e.g. `RUST_LOG=aoc2023::day5=trace`.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the solver for one or all days.
+    Run(RunArgs),
+    /// Checks the answers against `answers.toml` in the inputs directory.
+    Verify(VerifyArgs),
+    /// Times parsing and each part of one or all days.
+    Bench(BenchArgs),
+    /// Draws a day's grid to a BMP image.
+    Render(RenderArgs),
+    /// Writes the steps of a day's simulation as numbered BMP images.
+    Animate(AnimateArgs),
+    /// Prints a random input for a day.
+    Generate(GenerateArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    /// Day to run.
+    #[arg(long, required_unless_present = "all", value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Part to run, runs both parts if absent.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Input file, defaults to `dayN.txt` in the inputs directory.
+    #[arg(long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+    /// Runs every day.
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    /// One line per part.
+    Text,
+    /// A JSON array with the answer, timings and status of every part.
+    Json,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Day to verify, verifies every day if absent.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    /// Day to time, times every day if absent.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+    /// Untimed runs before timing.
+    #[arg(long, default_value_t = 1)]
+    warmup: usize,
+    /// Timed runs.
+    #[arg(long, default_value_t = 10)]
+    iterations: usize,
+}
+
+#[derive(Args)]
+struct RenderArgs {
+    /// Day to draw, one of 10, 14, 16, 17 and 21.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Input file, defaults to `dayN.txt` in the inputs directory.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Image file, defaults to `dayN.bmp`.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Pixels per side of a tile.
+    #[arg(long, default_value_t = 4)]
+    scale: u32,
+    /// Prints the grid with ANSI colours instead, days 10, 16 and 17 only.
+    #[arg(long, conflicts_with_all = ["output", "scale"])]
+    terminal: bool,
+}
+
+#[derive(Args)]
+struct AnimateArgs {
+    /// Day to animate, one of 14, 16 and 21.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// Input file, defaults to `dayN.txt` in the inputs directory.
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// Directory for the frames, defaults to `dayN`.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Keeps one step in `stride`.
+    #[arg(long, default_value_t = 1)]
+    stride: usize,
+    /// Pixels per side of a tile.
+    #[arg(long, default_value_t = 4)]
+    scale: u32,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Day to generate an input for.
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: u8,
+    /// The same seed always gives the same input.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+/// Loads the input, noting on stderr if it had to be normalized.
+fn load_input(day: u8, path: &Path) -> Result<String, input::InputError> {
+    let (input, changes) = input::read_path(day, path)?;
+    if !changes.is_unchanged() {
+        eprintln!("day {day}: input {}: {changes}", path.display());
+    }
+    Ok(input)
+}
+
+fn run_day(day: u8, parts: &[u8], input: &Path) -> Result<Vec<PartReport>, String> {
+    let entry = solution::day(day).ok_or_else(|| format!("day {day} not solved"))?;
+    if let [part] = parts {
+        if *part > entry.parts {
+            return Err(format!("day {day} has no part {part}"));
+        }
+    }
+    let input = load_input(day, input).map_err(|e| e.to_string())?;
+    Ok(report::run(entry, &input, parts))
+}
+
+fn print_text(reports: &[PartReport]) -> Result<(), String> {
+    let Some(first) = reports.first() else {
+        return Ok(());
+    };
+    let PartReport {
+        day,
+        title,
+        parse_time,
+        ..
+    } = first;
+    match first.status {
+        Status::Ok => {}
+        Status::ParseError => return Err(first.error.clone().unwrap_or_default()),
+        Status::Panic => return Err("panicked parsing the input".to_string()),
+    }
+    println!("day {day}: {title} (parsed in {parse_time:?})");
+    for r in reports {
+        let PartReport {
+            part,
+            status,
+            answer,
+            solve_time,
+            ..
+        } = r;
+        match (status, answer, solve_time) {
+            (Status::Ok, Some(ans), Some(elapsed)) => {
+                println!("day {day} part {part} = {ans} ({elapsed:?})")
+            }
+            _ => return Err(format!("part {part} panicked")),
+        }
+    }
+    Ok(())
+}
+
+fn run(inputs: &Path, args: RunArgs) -> Result<(), String> {
+    let RunArgs {
+        day,
+        part,
+        input,
+        all,
+        format,
+    } = args;
+    let parts = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    let days = match day {
+        _ if all => solution::DAYS
+            .iter()
+            .map(|e| (e.day, input::path(inputs, e.day)))
+            .collect(),
+        Some(day) => vec![(day, input.unwrap_or_else(|| input::path(inputs, day)))],
+        None => unreachable!("--day is required without --all"),
+    };
+    let mut reports = Vec::new();
+    for (day, input) in days {
+        let result = run_day(day, &parts, &input).and_then(|r| match format {
+            Format::Text => print_text(&r),
+            Format::Json => {
+                reports.extend(r);
+                Ok(())
+            }
+        });
+        match result {
+            Ok(()) => {}
+            Err(e) if all => eprintln!("day {day}: {e}"),
+            Err(e) => return Err(e),
+        }
+    }
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+    }
+    Ok(())
+}
+
+#[derive(Default)]
+struct Tally {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+}
+
+fn verify_day(
+    entry: &solution::Entry,
+    inputs: &Path,
+    answers: &answers::Answers,
+    tally: &mut Tally,
+) {
+    let day = entry.day;
+    let parts = 1..=entry.parts;
+    let input = match load_input(day, &input::path(inputs, day)) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("day {day}: missing, {e}");
+            tally.missing += parts.count();
+            return;
+        }
+    };
+    let input = match entry.parse(&input) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("day {day}: FAIL, {e}");
+            tally.failed += parts.count();
+            return;
+        }
+    };
+    for part in parts {
+        let ans = entry.solve(&input, part).unwrap().to_string();
+        match answers.verify(day, part, &ans) {
+            Verdict::Pass => {
+                println!("day {day} part {part}: pass");
+                tally.passed += 1;
+            }
+            Verdict::Fail(expected) => {
+                println!("day {day} part {part}: FAIL, got {ans} expected {expected}");
+                tally.failed += 1;
+            }
+            Verdict::Missing => {
+                println!("day {day} part {part}: missing, got {ans}");
+                tally.missing += 1;
+            }
+        }
+    }
+}
+
+fn verify(inputs: &Path, args: VerifyArgs) -> Result<(), String> {
+    let answers = answers::Answers::load_from(inputs).map_err(|e| e.to_string())?;
+    let entries = match args.day {
+        Some(day) => vec![solution::day(day).ok_or_else(|| format!("day {day} not solved"))?],
+        None => solution::DAYS.iter().collect(),
+    };
+    let mut tally = Tally::default();
+    for entry in entries {
+        verify_day(entry, inputs, &answers, &mut tally);
+    }
+    let Tally {
+        passed,
+        failed,
+        missing,
+    } = tally;
+    println!("{passed} passed, {failed} failed, {missing} missing");
+    if failed > 0 {
+        return Err(format!("{failed} answers failed"));
+    }
+    Ok(())
+}
+
+fn bench(inputs: &Path, args: BenchArgs) -> Result<(), String> {
+    let BenchArgs {
+        day,
+        warmup,
+        iterations,
+    } = args;
+    let entries = match day {
+        Some(day) => vec![solution::day(day).ok_or_else(|| format!("day {day} not solved"))?],
+        None => solution::DAYS.iter().collect(),
+    };
+    let mut rows = Vec::new();
+    for entry in entries {
+        let day = entry.day;
+        let timed = load_input(day, &input::path(inputs, day))
+            .map_err(|e| e.to_string())
+            .and_then(|input| {
+                bench::day(entry, &input, warmup, iterations).map_err(|e| e.to_string())
+            });
+        match timed {
+            Ok(r) => rows.extend(r),
+            Err(e) => eprintln!("day {day}: {e}"),
+        }
+    }
+    bench::print_table(&rows);
+    Ok(())
+}
+
+fn render(inputs: &Path, args: RenderArgs) -> Result<(), String> {
+    let RenderArgs {
+        day,
+        input,
+        output,
+        scale,
+        terminal,
+    } = args;
+    let entry = solution::day(day).ok_or_else(|| format!("day {day} not solved"))?;
+    let input = input.unwrap_or_else(|| input::path(inputs, day));
+    let input = load_input(day, &input).map_err(|e| e.to_string())?;
+    let parsed = entry.parse(&input).map_err(|e| e.to_string())?;
+    if terminal {
+        let drawn = entry
+            .terminal(&parsed)
+            .ok_or_else(|| format!("day {day} has nothing to draw in the terminal"))?;
+        print!("{drawn}");
+        return Ok(());
+    }
+    let image = entry
+        .render(&parsed)
+        .ok_or_else(|| format!("day {day} has nothing to draw"))?;
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("day{day}.bmp")));
+    image::scale(&image, scale)
+        .save(&output)
+        .map_err(|e| format!("{}: {e}", output.display()))?;
+    println!("day {day}: wrote {}", output.display());
+    Ok(())
+}
+
+fn animate(inputs: &Path, args: AnimateArgs) -> Result<(), String> {
+    let AnimateArgs {
+        day,
+        input,
+        output,
+        stride,
+        scale,
+    } = args;
+    let entry = solution::day(day).ok_or_else(|| format!("day {day} not solved"))?;
+    let input = input.unwrap_or_else(|| input::path(inputs, day));
+    let input = load_input(day, &input).map_err(|e| e.to_string())?;
+    let parsed = entry.parse(&input).map_err(|e| e.to_string())?;
+    let mut recorder = image::Recorder::new(stride);
+    entry.animate(&parsed, &mut recorder);
+    if recorder.frames().is_empty() {
+        return Err(format!("day {day} has nothing to animate"));
+    }
+    let output = output.unwrap_or_else(|| PathBuf::from(format!("day{day}")));
+    let frames = recorder
+        .save(&output, scale)
+        .map_err(|e| format!("{}: {e}", output.display()))?;
+    println!("day {day}: wrote {frames} frames to {}", output.display());
+    Ok(())
+}
+
+fn generate(args: GenerateArgs) -> Result<(), String> {
+    let GenerateArgs { day, seed } = args;
+    let input = generate::input(day, seed).ok_or_else(|| format!("no generator for day {day}"))?;
+    print!("{input}");
+    Ok(())
+}
+
+fn init_logging(verbose: u8) {
+    let level = match verbose {
+        0 => log::LevelFilter::Off,
+        1 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    };
+    env_logger::Builder::new()
+        .filter_module("aoc2023", level)
+        .parse_default_env()
+        .format_timestamp(None)
+        .init();
+}
+
+fn main() {
+    let Cli {
+        inputs,
+        verbose,
+        command,
+    } = Cli::parse();
+    init_logging(verbose);
+    let inputs = inputs.unwrap_or_else(input::dir);
+    let result = match command {
+        Command::Run(args) => run(&inputs, args),
+        Command::Verify(args) => verify(&inputs, args),
+        Command::Bench(args) => bench(&inputs, args),
+        Command::Render(args) => render(&inputs, args),
+        Command::Animate(args) => animate(&inputs, args),
+        Command::Generate(args) => generate(args),
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,201 @@
+use std::{fmt::Display, str::FromStr};
+
+use regex::{Captures, Regex};
+
+/// Error parsing a day's input, points at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number, in chars.
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self {
+            day,
+            line,
+            column,
+            text,
+            message,
+        } = self;
+        write!(
+            f,
+            "day {day} line {line} column {column}: {message} at {text:?}"
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A line of a day's input.
+#[derive(Debug, Copy, Clone)]
+pub struct Line<'a> {
+    pub day: u8,
+    /// 0-based line index.
+    pub index: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Error pointing at `at`, which must be a slice of this line, the
+    /// column falls back to the end of the line otherwise.
+    pub fn error(&self, at: &str, message: impl Display) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|o| *o <= self.text.len() && self.text.is_char_boundary(*o))
+            .unwrap_or(self.text.len());
+        ParseError {
+            day: self.day,
+            line: self.index + 1,
+            column: self.text[..offset].chars().count() + 1,
+            text: at.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn parse<T: FromStr>(&self, at: &str) -> Result<T, ParseError> {
+        T::from_str(at).map_err(|_| self.error(at, format!("expected {}", short_type_name::<T>())))
+    }
+
+    pub fn split_once(
+        &self,
+        at: &'a str,
+        delimiter: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        at.split_once(delimiter)
+            .ok_or_else(|| self.error(at, format!("expected {delimiter:?}")))
+    }
+
+    pub fn captures(&self, regex: &Regex) -> Result<Captures<'a>, ParseError> {
+        regex
+            .captures(self.text)
+            .ok_or_else(|| self.error(self.text, format!("doesn't match {regex}")))
+    }
+
+    /// Maps every char in the line with `f`, errors on the chars it returns
+    /// `None` for.
+    pub fn chars<'b, T>(
+        &'b self,
+        f: impl FnMut(char) -> Option<T> + 'b,
+    ) -> impl Iterator<Item = Result<T, ParseError>> + 'b {
+        self.chars_in(self.text, f)
+    }
+
+    /// Same as [`Line::chars`] for `at`, a slice of this line.
+    pub fn chars_in<'b, T>(
+        &'b self,
+        at: &'b str,
+        mut f: impl FnMut(char) -> Option<T> + 'b,
+    ) -> impl Iterator<Item = Result<T, ParseError>> + 'b {
+        at.char_indices().map(move |(i, c)| {
+            f(c).ok_or_else(|| {
+                self.error(&at[i..i + c.len_utf8()], format!("unexpected char {c:?}"))
+            })
+        })
+    }
+}
+
+/// Iterator over the lines of a day's input.
+#[derive(Debug, Clone)]
+pub struct Lines<'a> {
+    day: u8,
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    count: usize,
+}
+
+impl<'a> Lines<'a> {
+    pub fn new(day: u8, input: &'a str) -> Self {
+        Self {
+            day,
+            lines: input.lines().enumerate(),
+            count: input.lines().count(),
+        }
+    }
+
+    /// Returns the next line or an error at the end of the input.
+    pub fn expect(&mut self, what: &str) -> Result<Line<'a>, ParseError> {
+        match self.next() {
+            Some(l) => Ok(l),
+            None => Err(self.end_error(format!("expected {what}"))),
+        }
+    }
+
+    /// Error pointing past the last line, for things missing from the whole
+    /// input.
+    pub fn end_error(&self, message: impl Display) -> ParseError {
+        ParseError {
+            day: self.day,
+            line: self.count + 1,
+            column: 1,
+            text: String::new(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.next().map(|(index, text)| Line {
+            day: self.day,
+            index,
+            text,
+        })
+    }
+}
+
+pub fn lines(day: u8, input: &str) -> Lines<'_> {
+    Lines::new(day, input)
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_position() {
+        let mut lines = lines(3, "abc\nde fgh\n");
+        let _ = lines.next().unwrap();
+        let line = lines.next().unwrap();
+        let (_, b) = line.split_once(line.text, " ").unwrap();
+        let e = line.parse::<usize>(b).unwrap_err();
+        assert_eq!(
+            e,
+            ParseError {
+                day: 3,
+                line: 2,
+                column: 4,
+                text: "fgh".to_string(),
+                message: "expected usize".to_string(),
+            }
+        );
+        assert_eq!(
+            e.to_string(),
+            "day 3 line 2 column 4: expected usize at \"fgh\""
+        );
+        let e = lines.expect("more").unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+    }
+
+    #[test]
+    fn bad_char() {
+        let line = lines(10, "..\r").next().unwrap();
+        let e = line
+            .chars(|c| (c == '.').then_some(()))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 3, "\r"));
+    }
+}
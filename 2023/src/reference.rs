@@ -0,0 +1,1245 @@
+//! Slow, obviously correct solvers for small inputs, to check the real ones
+//! against. They read the raw input themselves and skip the shortcuts the
+//! real solvers take: no least common multiples, no area formulas. Day 14
+//! part 2 is the exception, it skips ahead once the spin cycle repeats like
+//! the real solver does, so only the tilting is checked independently.
+
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+
+use itertools::Itertools;
+
+/// The answer to `part` of `day` worked out the long way, `None` when there
+/// is no reference for it or the input is too big for one.
+pub fn solve(day: u8, part: u8, input: &str) -> Option<String> {
+    let solve = match day {
+        1 => day1,
+        2 => day2,
+        3 => day3,
+        4 => day4,
+        5 => day5,
+        6 => day6,
+        7 => day7,
+        8 => day8,
+        9 => day9,
+        10 => day10,
+        11 => day11,
+        12 => day12,
+        13 => day13,
+        14 => day14,
+        15 => day15,
+        16 => day16,
+        17 => day17,
+        18 => day18,
+        19 => day19,
+        20 => day20,
+        21 => day21,
+        22 => day22,
+        23 => day23,
+        24 => day24,
+        25 => day25,
+        _ => return None,
+    };
+    solve(part == 2, input)
+}
+
+fn numbers(s: &str) -> Vec<i64> {
+    let regex = regex::Regex::new("-?[0-9]+").unwrap();
+    regex
+        .find_iter(s)
+        .map(|m| m.as_str().parse().unwrap())
+        .collect()
+}
+
+fn grid(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|l| l.chars().collect()).collect()
+}
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+fn at(grid: &[Vec<char>], x: isize, y: isize) -> Option<char> {
+    let row = grid.get(usize::try_from(y).ok()?)?;
+    row.get(usize::try_from(x).ok()?).copied()
+}
+
+fn day1(part_2: bool, input: &str) -> Option<String> {
+    const WORDS: [&str; 10] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let sum: u32 = input
+        .lines()
+        .map(|line| {
+            let digits = (0..line.len())
+                .filter_map(|i| {
+                    let rest = &line[i..];
+                    let word = || WORDS.iter().position(|w| rest.starts_with(w));
+                    rest.chars()
+                        .next()?
+                        .to_digit(10)
+                        .or_else(|| word().filter(|_| part_2).map(|d| d as u32))
+                })
+                .collect_vec();
+            digits[0] * 10 + digits[digits.len() - 1]
+        })
+        .sum();
+    Some(sum.to_string())
+}
+
+fn day2(part_2: bool, input: &str) -> Option<String> {
+    let sum: u32 = input
+        .lines()
+        .map(|line| {
+            let (game, draws) = line.split_once(": ").unwrap();
+            let id: u32 = game["Game ".len()..].parse().unwrap();
+            let mut most = HashMap::new();
+            for draw in draws.split([';', ',']) {
+                let (count, colour) = draw.trim().split_once(' ').unwrap();
+                let most = most.entry(colour).or_insert(0);
+                *most = count.parse::<u32>().unwrap().max(*most);
+            }
+            let most = |colour| most.get(colour).copied().unwrap_or(0);
+            if part_2 {
+                most("red") * most("green") * most("blue")
+            } else if most("red") <= 12 && most("green") <= 13 && most("blue") <= 14 {
+                id
+            } else {
+                0
+            }
+        })
+        .sum();
+    Some(sum.to_string())
+}
+
+fn day3(part_2: bool, input: &str) -> Option<String> {
+    let grid = grid(input);
+    let symbol = |x, y| at(&grid, x, y).is_some_and(|c| c != '.' && !c.is_ascii_digit());
+    // Every number with the symbols around it.
+    let mut numbers = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let len = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if len == 0 {
+                x += 1;
+                continue;
+            }
+            let number: u64 = row[x..x + len].iter().collect::<String>().parse().unwrap();
+            let (y, left, right) = (y as isize, x as isize - 1, (x + len) as isize);
+            let symbols = (left..=right)
+                .cartesian_product(y - 1..=y + 1)
+                .filter(|(x, y)| symbol(*x, *y))
+                .collect_vec();
+            numbers.push((number, symbols));
+            x += len;
+        }
+    }
+    let sum: u64 = if part_2 {
+        let mut gears = HashMap::<_, Vec<u64>>::new();
+        for (number, symbols) in &numbers {
+            for (x, y) in symbols {
+                if grid[*y as usize][*x as usize] == '*' {
+                    gears.entry((x, y)).or_default().push(*number);
+                }
+            }
+        }
+        gears
+            .values()
+            .filter(|numbers| numbers.len() == 2)
+            .map(|numbers| numbers[0] * numbers[1])
+            .sum()
+    } else {
+        numbers
+            .iter()
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+            .sum()
+    };
+    Some(sum.to_string())
+}
+
+fn day4(part_2: bool, input: &str) -> Option<String> {
+    let matches = input
+        .lines()
+        .map(|line| {
+            let (_, card) = line.split_once(':').unwrap();
+            let (winning, have) = card.split_once('|').unwrap();
+            let winning = numbers(winning);
+            numbers(have).iter().filter(|n| winning.contains(n)).count()
+        })
+        .collect_vec();
+    if !part_2 {
+        let points: usize = matches
+            .iter()
+            .map(|m| if *m == 0 { 0 } else { 1 << (m - 1) })
+            .sum();
+        return Some(points.to_string());
+    }
+    // Every copy is scratched one at a time.
+    let mut pile = (0..matches.len()).collect_vec();
+    let mut scratched = 0usize;
+    while let Some(card) = pile.pop() {
+        scratched += 1;
+        pile.extend(card + 1..(card + 1 + matches[card]).min(matches.len()));
+    }
+    Some(scratched.to_string())
+}
+
+fn day5(part_2: bool, input: &str) -> Option<String> {
+    let mut blocks = input.split("\n\n");
+    let seeds = numbers(blocks.next().unwrap());
+    let stages = blocks
+        .map(|block| {
+            block
+                .lines()
+                .skip(1)
+                .map(|l| numbers(l).into_iter().collect_tuple().unwrap())
+                .collect_vec()
+        })
+        .collect_vec();
+    let location = |seed: i64| {
+        stages.iter().fold(seed, |value, ranges| {
+            ranges
+                .iter()
+                .find(|(_, src, len)| (*src..src + len).contains(&value))
+                .map_or(value, |(dst, src, _)| dst + value - src)
+        })
+    };
+    let lowest = if part_2 {
+        seeds
+            .chunks(2)
+            .flat_map(|pair| pair[0]..pair[0] + pair[1])
+            .map(location)
+            .min()
+    } else {
+        seeds.iter().copied().map(location).min()
+    };
+    lowest.map(|l| l.to_string())
+}
+
+fn day6(part_2: bool, input: &str) -> Option<String> {
+    let (times, records) = input
+        .lines()
+        .map(|l| {
+            if part_2 {
+                vec![l
+                    .chars()
+                    .filter(char::is_ascii_digit)
+                    .collect::<String>()
+                    .parse()
+                    .unwrap()]
+            } else {
+                numbers(l)
+            }
+        })
+        .collect_tuple()
+        .unwrap();
+    let ways: usize = times
+        .iter()
+        .zip(&records)
+        .map(|(time, record)| {
+            (0..=*time)
+                .filter(|hold| (time - hold) * hold > *record)
+                .count()
+        })
+        .product();
+    Some(ways.to_string())
+}
+
+fn day7(part_2: bool, input: &str) -> Option<String> {
+    const CARDS: &str = "23456789TJQKA";
+    let kind = |hand: &[char]| {
+        let counts = hand
+            .iter()
+            .counts()
+            .into_values()
+            .sorted()
+            .rev()
+            .collect_vec();
+        match counts[..] {
+            [5] => 6,
+            [4, 1] => 5,
+            [3, 2] => 4,
+            [3, 1, 1] => 3,
+            [2, 2, 1] => 2,
+            [2, 1, 1, 1] => 1,
+            _ => 0,
+        }
+    };
+    let hands = input
+        .lines()
+        .map(|l| {
+            let (hand, bid) = l.split_once(' ').unwrap();
+            let hand = hand.chars().collect_vec();
+            let best = if part_2 {
+                // Every jack tries every other card.
+                hand.iter()
+                    .map(|c| match c {
+                        'J' => CARDS.chars().filter(|c| *c != 'J').collect_vec(),
+                        c => vec![*c],
+                    })
+                    .multi_cartesian_product()
+                    .map(|h| kind(&h))
+                    .max()
+                    .unwrap()
+            } else {
+                kind(&hand)
+            };
+            let values = hand
+                .iter()
+                .map(|c| match c {
+                    'J' if part_2 => -1,
+                    c => CARDS.find(*c).unwrap() as i32,
+                })
+                .collect_vec();
+            ((best, values), bid.parse::<usize>().unwrap())
+        })
+        .sorted()
+        .collect_vec();
+    let winnings: usize = hands
+        .iter()
+        .enumerate()
+        .map(|(rank, (_, bid))| (rank + 1) * bid)
+        .sum();
+    Some(winnings.to_string())
+}
+
+fn day8(part_2: bool, input: &str) -> Option<String> {
+    let mut lines = input.lines();
+    let dirs = lines.next().unwrap().chars().collect_vec();
+    let regex = regex::Regex::new(r"(\w+) = \((\w+), (\w+)\)").unwrap();
+    let nodes = lines
+        .filter_map(|l| regex.captures(l))
+        .map(|c| (c[1].to_string(), (c[2].to_string(), c[3].to_string())))
+        .collect::<HashMap<_, _>>();
+    // Every ghost steps together until they all stand on a Z.
+    let mut ghosts = if part_2 {
+        nodes.keys().filter(|n| n.ends_with('A')).collect_vec()
+    } else {
+        vec![nodes.get_key_value("AAA").unwrap().0]
+    };
+    let done = |n: &String| if part_2 { n.ends_with('Z') } else { n == "ZZZ" };
+    for steps in 0..10_000_000 {
+        if ghosts.iter().all(|g| done(g)) {
+            return Some(steps.to_string());
+        }
+        for g in ghosts.iter_mut() {
+            let (left, right) = &nodes[*g];
+            *g = if dirs[steps % dirs.len()] == 'L' {
+                left
+            } else {
+                right
+            };
+        }
+    }
+    None
+}
+
+fn day9(part_2: bool, input: &str) -> Option<String> {
+    let sum: i64 = input
+        .lines()
+        .map(|l| {
+            let mut rows = vec![numbers(l)];
+            while rows.last().unwrap().iter().any(|v| *v != 0) {
+                let next = rows
+                    .last()
+                    .unwrap()
+                    .windows(2)
+                    .map(|w| w[1] - w[0])
+                    .collect();
+                rows.push(next);
+            }
+            rows.iter().rev().fold(0, |below, row| {
+                if part_2 {
+                    row[0] - below
+                } else {
+                    row[row.len() - 1] + below
+                }
+            })
+        })
+        .sum();
+    Some(sum.to_string())
+}
+
+fn day10(part_2: bool, input: &str) -> Option<String> {
+    let mut grid = grid(input);
+    let pipe = |c| match c {
+        '|' => vec![(0, -1), (0, 1)],
+        '-' => vec![(-1, 0), (1, 0)],
+        'L' => vec![(0, -1), (1, 0)],
+        'J' => vec![(0, -1), (-1, 0)],
+        '7' => vec![(0, 1), (-1, 0)],
+        'F' => vec![(0, 1), (1, 0)],
+        _ => vec![],
+    };
+    let (sx, sy) = (0..grid.len())
+        .flat_map(|y| (0..grid[y].len()).map(move |x| (x, y)))
+        .find(|(x, y)| grid[*y][*x] == 'S')
+        .unwrap();
+    let (sx, sy) = (sx as isize, sy as isize);
+    let connected = ORTHOGONAL
+        .into_iter()
+        .filter(|(dx, dy)| {
+            let c = at(&grid, sx + dx, sy + dy).unwrap_or('.');
+            pipe(c).contains(&(-dx, -dy))
+        })
+        .collect_vec();
+    grid[sy as usize][sx as usize] = "|-LJ7F"
+        .chars()
+        .find(|c| connected.iter().all(|d| pipe(*c).contains(d)))
+        .unwrap();
+
+    let mut tiles = vec![(sx, sy)];
+    let mut from = (sx, sy);
+    loop {
+        let (x, y) = *tiles.last().unwrap();
+        let next = pipe(grid[y as usize][x as usize])
+            .into_iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .find(|n| *n != from)
+            .unwrap();
+        if next == (sx, sy) {
+            break;
+        }
+        from = (x, y);
+        tiles.push(next);
+    }
+    if !part_2 {
+        return Some((tiles.len() / 2).to_string());
+    }
+
+    // Every tile becomes three by three with the pipes of the loop drawn
+    // through it, then water poured in from the outside.
+    let (cols, rows) = (grid[0].len() as isize * 3 + 2, grid.len() as isize * 3 + 2);
+    let mut wall = HashSet::new();
+    for (x, y) in &tiles {
+        let (cx, cy) = (x * 3 + 2, y * 3 + 2);
+        wall.insert((cx, cy));
+        for (dx, dy) in pipe(grid[*y as usize][*x as usize]) {
+            wall.insert((cx + dx, cy + dy));
+        }
+    }
+    let mut wet = HashSet::from([(0, 0)]);
+    let mut queue = VecDeque::from([(0, 0)]);
+    while let Some((x, y)) = queue.pop_front() {
+        for (dx, dy) in ORTHOGONAL {
+            let n = (x + dx, y + dy);
+            if (0..cols).contains(&n.0)
+                && (0..rows).contains(&n.1)
+                && !wall.contains(&n)
+                && wet.insert(n)
+            {
+                queue.push_back(n);
+            }
+        }
+    }
+    let on_loop = tiles.into_iter().collect::<HashSet<_>>();
+    let enclosed = (0..grid.len() as isize)
+        .cartesian_product(0..grid[0].len() as isize)
+        .filter(|(y, x)| !on_loop.contains(&(*x, *y)) && !wet.contains(&(x * 3 + 2, y * 3 + 2)))
+        .count();
+    Some(enclosed.to_string())
+}
+
+fn day11(part_2: bool, input: &str) -> Option<String> {
+    let grid = grid(input);
+    let factor = if part_2 { 1_000_000 } else { 2 };
+    let galaxies = (0..grid.len())
+        .cartesian_product(0..grid[0].len())
+        .filter(|(y, x)| grid[*y][*x] == '#')
+        .collect_vec();
+    let empty_row = |y: usize| grid[y].iter().all(|c| *c == '.');
+    let empty_col = |x: usize| grid.iter().all(|row| row[x] == '.');
+    // Walk every pair apart one row and one column at a time.
+    let sum: usize = galaxies
+        .iter()
+        .tuple_combinations()
+        .map(|((ay, ax), (by, bx))| {
+            let rows = (*ay.min(by)..*ay.max(by)).map(|y| if empty_row(y) { factor } else { 1 });
+            let cols = (*ax.min(bx)..*ax.max(bx)).map(|x| if empty_col(x) { factor } else { 1 });
+            rows.sum::<usize>() + cols.sum::<usize>()
+        })
+        .sum();
+    Some(sum.to_string())
+}
+
+fn day12(part_2: bool, input: &str) -> Option<String> {
+    // Unfolded rows have far too many arrangements to try.
+    if part_2 {
+        return None;
+    }
+    fn groups(springs: &[char]) -> Vec<usize> {
+        springs
+            .split(|c| *c != '#')
+            .map(<[char]>::len)
+            .filter(|l| *l > 0)
+            .collect()
+    }
+    fn arrangements(springs: &mut Vec<char>, i: usize, want: &[usize]) -> usize {
+        let Some(unknown) = springs[i..].iter().position(|c| *c == '?') else {
+            return usize::from(groups(springs) == want);
+        };
+        let damaged = springs.iter().filter(|c| **c == '#').count();
+        if damaged > want.iter().sum() {
+            return 0;
+        }
+        let unknown = i + unknown;
+        ['#', '.']
+            .into_iter()
+            .map(|c| {
+                springs[unknown] = c;
+                let n = arrangements(springs, unknown + 1, want);
+                springs[unknown] = '?';
+                n
+            })
+            .sum()
+    }
+    let sum: usize = input
+        .lines()
+        .map(|l| {
+            let (springs, want) = l.split_once(' ').unwrap();
+            let want = numbers(want).into_iter().map(|n| n as usize).collect_vec();
+            arrangements(&mut springs.chars().collect(), 0, &want)
+        })
+        .sum();
+    Some(sum.to_string())
+}
+
+fn day13(part_2: bool, input: &str) -> Option<String> {
+    let smudges = usize::from(part_2);
+    // Lines above the first mirror with exactly `smudges` differences.
+    let mirror = |map: &[Vec<char>]| {
+        (1..map.len()).find(|m| {
+            let differences: usize = (0..*m)
+                .rev()
+                .zip(*m..map.len())
+                .map(|(a, b)| map[a].iter().zip(&map[b]).filter(|(x, y)| x != y).count())
+                .sum();
+            differences == smudges
+        })
+    };
+    let sum: usize = input
+        .split("\n\n")
+        .map(|block| {
+            let map = grid(block);
+            let transposed = (0..map[0].len())
+                .map(|x| map.iter().map(|row| row[x]).collect())
+                .collect_vec();
+            mirror(&map)
+                .map(|m| m * 100)
+                .or_else(|| mirror(&transposed))
+                .unwrap()
+        })
+        .sum();
+    Some(sum.to_string())
+}
+
+fn day14(part_2: bool, input: &str) -> Option<String> {
+    // Rolls every rock a tile at a time until none can move.
+    fn tilt(grid: &mut [Vec<char>], (dx, dy): (isize, isize)) {
+        let mut moved = true;
+        while moved {
+            moved = false;
+            for y in 0..grid.len() as isize {
+                for x in 0..grid[0].len() as isize {
+                    if grid[y as usize][x as usize] == 'O' && at(grid, x + dx, y + dy) == Some('.')
+                    {
+                        grid[y as usize][x as usize] = '.';
+                        grid[(y + dy) as usize][(x + dx) as usize] = 'O';
+                        moved = true;
+                    }
+                }
+            }
+        }
+    }
+    fn load(grid: &[Vec<char>]) -> usize {
+        grid.iter()
+            .enumerate()
+            .map(|(y, row)| row.iter().filter(|c| **c == 'O').count() * (grid.len() - y))
+            .sum()
+    }
+    let mut grid = grid(input);
+    if !part_2 {
+        tilt(&mut grid, (0, -1));
+        return Some(load(&grid).to_string());
+    }
+    // A billion spins are too many to run, this is the same cycle skipping
+    // as the real solver.
+    let cycles = 1_000_000_000;
+    let mut seen = vec![grid.clone()];
+    loop {
+        for d in [(0, -1), (-1, 0), (0, 1), (1, 0)] {
+            tilt(&mut grid, d);
+        }
+        if let Some(start) = seen.iter().position(|g| *g == grid) {
+            let len = seen.len() - start;
+            let end = &seen[start + (cycles - start) % len];
+            return Some(load(end).to_string());
+        }
+        seen.push(grid.clone());
+    }
+}
+
+fn day15(part_2: bool, input: &str) -> Option<String> {
+    let hash = |s: &str| s.bytes().fold(0, |h, b| (h + b as usize) * 17 % 256);
+    let steps = input.trim().split(',').collect_vec();
+    if !part_2 {
+        return Some(steps.iter().map(|s| hash(s)).sum::<usize>().to_string());
+    }
+    let mut boxes = vec![Vec::<(&str, usize)>::new(); 256];
+    for step in steps {
+        if let Some(label) = step.strip_suffix('-') {
+            boxes[hash(label)].retain(|(l, _)| *l != label);
+        } else {
+            let (label, focal) = step.split_once('=').unwrap();
+            let focal = focal.parse().unwrap();
+            let lenses = &mut boxes[hash(label)];
+            match lenses.iter_mut().find(|(l, _)| *l == label) {
+                Some(lens) => lens.1 = focal,
+                None => lenses.push((label, focal)),
+            }
+        }
+    }
+    let power: usize = boxes
+        .iter()
+        .enumerate()
+        .flat_map(|(b, lenses)| {
+            lenses
+                .iter()
+                .enumerate()
+                .map(move |(slot, (_, focal))| (b + 1) * (slot + 1) * focal)
+        })
+        .sum();
+    Some(power.to_string())
+}
+
+fn day16(part_2: bool, input: &str) -> Option<String> {
+    let grid = grid(input);
+    let energized = |start: (isize, isize, isize, isize)| {
+        let mut seen = HashSet::from([start]);
+        let mut beams = vec![start];
+        while let Some((x, y, dx, dy)) = beams.pop() {
+            let turns = match at(&grid, x, y).unwrap() {
+                '/' => vec![(-dy, -dx)],
+                '\\' => vec![(dy, dx)],
+                '-' if dy != 0 => vec![(-1, 0), (1, 0)],
+                '|' if dx != 0 => vec![(0, -1), (0, 1)],
+                _ => vec![(dx, dy)],
+            };
+            for (dx, dy) in turns {
+                let next = (x + dx, y + dy, dx, dy);
+                if at(&grid, next.0, next.1).is_some() && seen.insert(next) {
+                    beams.push(next);
+                }
+            }
+        }
+        seen.iter().map(|(x, y, _, _)| (*x, *y)).unique().count()
+    };
+    if !part_2 {
+        return Some(energized((0, 0, 1, 0)).to_string());
+    }
+    let (cols, rows) = (grid[0].len() as isize, grid.len() as isize);
+    let starts = (0..rows)
+        .flat_map(|y| [(0, y, 1, 0), (cols - 1, y, -1, 0)])
+        .chain((0..cols).flat_map(|x| [(x, 0, 0, 1), (x, rows - 1, 0, -1)]));
+    starts.map(energized).max().map(|e| e.to_string())
+}
+
+fn day17(part_2: bool, input: &str) -> Option<String> {
+    let grid = grid(input);
+    let loss = |x: isize, y: isize| at(&grid, x, y).and_then(|c| c.to_digit(10));
+    let (min, max) = if part_2 { (4, 10) } else { (1, 3) };
+    let exit = (grid[0].len() as isize - 1, grid.len() as isize - 1);
+    // Plain Dijkstra over position, heading and blocks gone straight, the
+    // crucible can set off in any direction.
+    let mut best = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for d in 0..4 {
+        queue.push(std::cmp::Reverse((0, (0, 0), d, 0)));
+    }
+    while let Some(std::cmp::Reverse((cost, (x, y), d, straight))) = queue.pop() {
+        if (x, y) == exit && straight >= min {
+            return Some(cost.to_string());
+        }
+        if best.get(&((x, y), d, straight)).is_some_and(|b| *b <= cost) {
+            continue;
+        }
+        best.insert(((x, y), d, straight), cost);
+        for (turn, (dx, dy)) in ORTHOGONAL.into_iter().enumerate() {
+            let same = turn == d;
+            let back = (turn + 2) % 4 == d && straight > 0;
+            let allowed = if same {
+                straight < max
+            } else {
+                straight == 0 || straight >= min
+            };
+            if back || !allowed {
+                continue;
+            }
+            let Some(l) = loss(x + dx, y + dy) else {
+                continue;
+            };
+            let straight = if same { straight + 1 } else { 1 };
+            queue.push(std::cmp::Reverse((
+                cost + l,
+                (x + dx, y + dy),
+                turn,
+                straight,
+            )));
+        }
+    }
+    None
+}
+
+fn day18(part_2: bool, input: &str) -> Option<String> {
+    let digs = input.lines().map(|l| {
+        let parts = l.split(' ').collect_vec();
+        if part_2 {
+            let hex = parts[2].trim_matches(|c| "(#)".contains(c));
+            let len = i64::from_str_radix(&hex[..5], 16).unwrap();
+            (
+                "RDLU".as_bytes()[hex[5..].parse::<usize>().unwrap()] as char,
+                len,
+            )
+        } else {
+            (parts[0].chars().next().unwrap(), parts[1].parse().unwrap())
+        }
+    });
+    let mut corners = vec![(0i64, 0i64)];
+    for (d, len) in digs {
+        let (x, y) = *corners.last().unwrap();
+        corners.push(match d {
+            'R' => (x + len, y),
+            'L' => (x - len, y),
+            'D' => (x, y + len),
+            _ => (x, y - len),
+        });
+    }
+    // Squeezes the runs of cubic metres between corners into single cells,
+    // floods the outside and adds up what is left.
+    let cuts = |coord: fn(&(i64, i64)) -> i64| {
+        let (lo, hi) = corners.iter().map(coord).minmax().into_option().unwrap();
+        corners
+            .iter()
+            .flat_map(|c| [coord(c), coord(c) + 1])
+            .chain([lo - 1, hi + 2])
+            .sorted()
+            .dedup()
+            .collect_vec()
+    };
+    let (xs, ys) = (cuts(|c| c.0), cuts(|c| c.1));
+    let cell = |cuts: &[i64], v: i64| cuts.binary_search(&v).unwrap();
+    let mut trench = HashSet::new();
+    for (a, b) in corners.iter().tuple_windows() {
+        let (x0, x1) = (cell(&xs, a.0.min(b.0)), cell(&xs, a.0.max(b.0)));
+        let (y0, y1) = (cell(&ys, a.1.min(b.1)), cell(&ys, a.1.max(b.1)));
+        trench.extend((x0..=x1).cartesian_product(y0..=y1));
+    }
+    let (cols, rows) = (xs.len() - 1, ys.len() - 1);
+    let mut outside = HashSet::from([(0, 0)]);
+    let mut queue = vec![(0usize, 0usize)];
+    while let Some((x, y)) = queue.pop() {
+        for (dx, dy) in ORTHOGONAL {
+            let (Some(nx), Some(ny)) = (x.checked_add_signed(dx), y.checked_add_signed(dy)) else {
+                continue;
+            };
+            if nx < cols && ny < rows && !trench.contains(&(nx, ny)) && outside.insert((nx, ny)) {
+                queue.push((nx, ny));
+            }
+        }
+    }
+    let lagoon: i64 = (0..cols)
+        .cartesian_product(0..rows)
+        .filter(|c| !outside.contains(c))
+        .map(|(x, y)| (xs[x + 1] - xs[x]) * (ys[y + 1] - ys[y]))
+        .sum();
+    Some(lagoon.to_string())
+}
+
+fn day19(part_2: bool, input: &str) -> Option<String> {
+    let (workflows, parts) = input.split_once("\n\n").unwrap();
+    let rule = regex::Regex::new(r"^([xmas])([<>])([0-9]+):(\w+)$").unwrap();
+    let workflows = workflows
+        .lines()
+        .map(|l| {
+            let (name, rules) = l.trim_end_matches('}').split_once('{').unwrap();
+            let rules = rules
+                .split(',')
+                .map(|r| match rule.captures(r) {
+                    Some(c) => {
+                        let category = "xmas".find(&c[1]).unwrap();
+                        (
+                            Some((category, &c[2] == "<", c[3].parse::<u64>().unwrap())),
+                            c.get(4).unwrap().as_str(),
+                        )
+                    }
+                    None => (None, r),
+                })
+                .collect_vec();
+            (name, rules)
+        })
+        .collect::<HashMap<_, _>>();
+    let accepted = |part: [u64; 4]| {
+        let mut at = "in";
+        loop {
+            at = workflows[at]
+                .iter()
+                .find(|(cond, _)| match cond {
+                    None => true,
+                    Some((c, true, v)) => part[*c] < *v,
+                    Some((c, false, v)) => part[*c] > *v,
+                })
+                .unwrap()
+                .1;
+            match at {
+                "A" => break true,
+                "R" => break false,
+                _ => {}
+            }
+        }
+    };
+    if !part_2 {
+        let sum: u64 = parts
+            .lines()
+            .map(|l| numbers(l).into_iter().map(|n| n as u64).collect_vec())
+            .filter(|p| accepted([p[0], p[1], p[2], p[3]]))
+            .map(|p| p.iter().sum::<u64>())
+            .sum();
+        return Some(sum.to_string());
+    }
+    // Ratings between two thresholds go the same way, so one of each run of
+    // ratings stands for the whole run.
+    let runs = (0..4)
+        .map(|category| {
+            workflows
+                .values()
+                .flatten()
+                .filter_map(|(cond, _)| *cond)
+                .filter(|(c, _, _)| *c == category)
+                .map(|(_, less, v)| if less { v } else { v + 1 })
+                .chain([1, 4001])
+                .sorted()
+                .dedup()
+                .tuple_windows::<(u64, u64)>()
+                .collect_vec()
+        })
+        .collect_vec();
+    let combinations: u64 = runs
+        .iter()
+        .map(|r| r.iter())
+        .multi_cartesian_product()
+        .filter(|runs| accepted([runs[0].0, runs[1].0, runs[2].0, runs[3].0]))
+        .map(|runs| runs.iter().map(|(a, b)| b - a).product::<u64>())
+        .sum();
+    Some(combinations.to_string())
+}
+
+fn day20(part_2: bool, input: &str) -> Option<String> {
+    let modules = input
+        .lines()
+        .map(|l| {
+            let (name, outputs) = l.split_once(" -> ").unwrap();
+            let (kind, name) = match name.chars().next() {
+                Some(k @ ('%' | '&')) => (k, &name[1..]),
+                _ => ('b', name),
+            };
+            (name, (kind, outputs.split(", ").collect_vec()))
+        })
+        .collect::<HashMap<_, _>>();
+    let mut on = HashSet::new();
+    let mut memory = HashMap::<&str, HashMap<&str, bool>>::new();
+    for (name, (_, outputs)) in &modules {
+        for o in outputs {
+            if modules.get(o).is_some_and(|(k, _)| *k == '&') {
+                memory.entry(*o).or_default().insert(*name, false);
+            }
+        }
+    }
+    let mut counts = [0usize; 2];
+    // Pulses in the order they are sent, one press after the other.
+    for press in 1..=100_000usize {
+        let mut queue = VecDeque::from([("button", "broadcaster", false)]);
+        while let Some((from, to, high)) = queue.pop_front() {
+            counts[usize::from(high)] += 1;
+            if part_2 && to == "rx" && !high {
+                return Some(press.to_string());
+            }
+            let Some((kind, outputs)) = modules.get(to) else {
+                continue;
+            };
+            let send = match kind {
+                '%' if high => continue,
+                '%' => on.insert(to) || !on.remove(to),
+                '&' => {
+                    let inputs = memory.get_mut(to).unwrap();
+                    inputs.insert(from, high);
+                    !inputs.values().all(|h| *h)
+                }
+                _ => high,
+            };
+            queue.extend(outputs.iter().map(|o| (to, *o, send)));
+        }
+        if !part_2 && press == 1000 {
+            return Some((counts[0] * counts[1]).to_string());
+        }
+    }
+    None
+}
+
+fn day21(part_2: bool, input: &str) -> Option<String> {
+    // 26501365 steps cover far too many maps, the tiled simulation in day21
+    // checks the shortcut on fewer.
+    if part_2 {
+        return None;
+    }
+    let steps = 64;
+    let grid = grid(input);
+    let start = (0..grid.len())
+        .cartesian_product(0..grid[0].len())
+        .find(|(y, x)| grid[*y][*x] == 'S')
+        .map(|(y, x)| (x as isize, y as isize))
+        .unwrap();
+    // A plot reached in fewer steps of the same parity can be stepped off
+    // and back on.
+    let mut distance = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some((x, y)) = queue.pop_front() {
+        let d = distance[&(x, y)];
+        if d == steps {
+            continue;
+        }
+        for (dx, dy) in ORTHOGONAL {
+            let n = (x + dx, y + dy);
+            if at(&grid, n.0, n.1).is_some_and(|c| c != '#') && !distance.contains_key(&n) {
+                distance.insert(n, d + 1);
+                queue.push_back(n);
+            }
+        }
+    }
+    let plots = distance.values().filter(|d| *d % 2 == steps % 2).count();
+    Some(plots.to_string())
+}
+
+fn day22(part_2: bool, input: &str) -> Option<String> {
+    type Cube = (i64, i64, i64);
+    let bricks = input
+        .lines()
+        .map(|l| {
+            let [x0, y0, z0, x1, y1, z1] = numbers(l)[..] else {
+                panic!("bad brick {l}");
+            };
+            (x0.min(x1)..=x0.max(x1))
+                .cartesian_product(y0.min(y1)..=y0.max(y1))
+                .cartesian_product(z0.min(z1)..=z0.max(z1))
+                .map(|((x, y), z)| (x, y, z))
+                .collect_vec()
+        })
+        .collect_vec();
+    // Drops bricks a cube at a time until none moves, returns which moved.
+    fn settle(bricks: &mut [Vec<Cube>]) -> HashSet<usize> {
+        let mut filled = bricks
+            .iter()
+            .enumerate()
+            .flat_map(|(i, b)| b.iter().map(move |c| (*c, i)))
+            .collect::<HashMap<_, _>>();
+        let mut moved = HashSet::new();
+        loop {
+            let mut any = false;
+            for (i, brick) in bricks.iter_mut().enumerate() {
+                let free = brick.iter().all(|(x, y, z)| {
+                    *z > 1 && filled.get(&(*x, *y, z - 1)).is_none_or(|j| *j == i)
+                });
+                if free {
+                    brick.iter().for_each(|c| {
+                        filled.remove(c);
+                    });
+                    brick.iter_mut().for_each(|c| c.2 -= 1);
+                    filled.extend(brick.iter().map(|c| (*c, i)));
+                    moved.insert(i);
+                    any = true;
+                }
+            }
+            if !any {
+                return moved;
+            }
+        }
+    }
+    let mut settled = bricks;
+    settle(&mut settled);
+    let falls = (0..settled.len()).map(|i| {
+        let mut without = settled.clone();
+        without.remove(i);
+        settle(&mut without).len()
+    });
+    let answer = if part_2 {
+        falls.sum::<usize>()
+    } else {
+        falls.filter(|n| *n == 0).count()
+    };
+    Some(answer.to_string())
+}
+
+fn day23(part_2: bool, input: &str) -> Option<String> {
+    let grid = grid(input);
+    let start = (grid[0].iter().position(|c| *c == '.').unwrap() as isize, 0);
+    let bottom = grid.len() as isize - 1;
+    // Every simple path, one tile at a time.
+    fn longest(
+        grid: &[Vec<char>],
+        (x, y): (isize, isize),
+        seen: &mut HashSet<(isize, isize)>,
+        slopes: bool,
+        bottom: isize,
+    ) -> Option<usize> {
+        if y == bottom {
+            return Some(0);
+        }
+        let ways = match at(grid, x, y).unwrap() {
+            '^' if slopes => vec![(0, -1)],
+            '>' if slopes => vec![(1, 0)],
+            'v' if slopes => vec![(0, 1)],
+            '<' if slopes => vec![(-1, 0)],
+            _ => ORTHOGONAL.to_vec(),
+        };
+        let mut best = None;
+        for (dx, dy) in ways {
+            let n = (x + dx, y + dy);
+            if at(grid, n.0, n.1).is_none_or(|c| c == '#') || !seen.insert(n) {
+                continue;
+            }
+            if let Some(len) = longest(grid, n, seen, slopes, bottom) {
+                best = best.max(Some(len + 1));
+            }
+            seen.remove(&n);
+        }
+        best
+    }
+    longest(&grid, start, &mut HashSet::from([start]), !part_2, bottom).map(|l| l.to_string())
+}
+
+fn day24(part_2: bool, input: &str) -> Option<String> {
+    let hail = input
+        .lines()
+        .map(|l| {
+            let n = numbers(l).into_iter().map(i128::from).collect_vec();
+            ([n[0], n[1], n[2]], [n[3], n[4], n[5]])
+        })
+        .collect_vec();
+    // Where `a` and `b` cross in x and y as times along each, scaled by the
+    // returned divisor, `None` when they are parallel.
+    let cross = |(pa, va): &([i128; 3], [i128; 3]), (pb, vb): &([i128; 3], [i128; 3])| {
+        let det = va[0] * vb[1] - va[1] * vb[0];
+        let (dx, dy) = (pb[0] - pa[0], pb[1] - pa[1]);
+        (det != 0).then(|| (dx * vb[1] - dy * vb[0], dx * va[1] - dy * va[0], det))
+    };
+    if !part_2 {
+        let (lo, hi) = (200_000_000_000_000i128, 400_000_000_000_000i128);
+        let count = hail
+            .iter()
+            .tuple_combinations()
+            .filter(|(a, b)| {
+                let Some((t, s, det)) = cross(a, b) else {
+                    return false;
+                };
+                let sign = det.signum();
+                let (t, s, det) = (t * sign, s * sign, det * sign);
+                let (p, v) = a;
+                let within = |i: usize| (lo * det..=hi * det).contains(&(p[i] * det + v[i] * t));
+                t >= 0 && s >= 0 && within(0) && within(1)
+            })
+            .count();
+        return Some(count.to_string());
+    }
+    // Seen from a rock moving at `v` the hail all flies through the point
+    // the rock is thrown from, try every small `v` in x and y.
+    let range = 300;
+    for (vx, vy) in (-range..=range).cartesian_product(-range..=range) {
+        let moved = hail
+            .iter()
+            .map(|(p, v)| (*p, [v[0] - vx, v[1] - vy, v[2]]))
+            .collect_vec();
+        let Some((j, (t, _, det))) =
+            (1..moved.len()).find_map(|j| Some((j, cross(&moved[0], &moved[j])?)))
+        else {
+            continue;
+        };
+        if t % det != 0 {
+            continue;
+        }
+        let t0 = t / det;
+        let (p0, v0) = moved[0];
+        let (x, y) = (p0[0] + v0[0] * t0, p0[1] + v0[1] * t0);
+        // Time each hail reaches (x, y), all of them must.
+        let times = moved
+            .iter()
+            .map(|(p, v)| {
+                let axis = if v[0] != 0 { 0 } else { 1 };
+                let target = [x, y][axis];
+                if v[axis] == 0 || (target - p[axis]) % v[axis] != 0 {
+                    return None;
+                }
+                let t = (target - p[axis]) / v[axis];
+                (t >= 0 && p[0] + v[0] * t == x && p[1] + v[1] * t == y).then_some(t)
+            })
+            .collect::<Option<Vec<_>>>();
+        let Some(times) = times else {
+            continue;
+        };
+        let (t1, (p1, v1)) = (times[j], hail[j]);
+        let (z0, z1) = (p0[2] + v0[2] * t0, p1[2] + v1[2] * t1);
+        if t0 == t1 || (z1 - z0) % (t1 - t0) != 0 {
+            continue;
+        }
+        let vz = (z1 - z0) / (t1 - t0);
+        let z = z0 - vz * t0;
+        let hits = hail
+            .iter()
+            .zip(&times)
+            .all(|((p, v), t)| p[2] + v[2] * t == z + vz * t);
+        if hits {
+            return Some((x + y + z).to_string());
+        }
+    }
+    None
+}
+
+fn day25(part_2: bool, input: &str) -> Option<String> {
+    if part_2 {
+        return None;
+    }
+    let mut names = HashMap::new();
+    let mut wires = Vec::new();
+    for l in input.lines() {
+        let (a, others) = l.split_once(": ").unwrap();
+        for b in others.split(' ') {
+            let mut id = |n| {
+                let len = names.len();
+                *names.entry(n).or_insert(len)
+            };
+            wires.push((id(a), id(b)));
+        }
+    }
+    let n = names.len();
+    // Cuts every three wires and keeps the cuts that leave two groups.
+    let sizes = (0..wires.len())
+        .tuple_combinations()
+        .filter_map(|(a, b, c)| {
+            let mut links = vec![Vec::new(); n];
+            for (i, (x, y)) in wires.iter().enumerate() {
+                if i != a && i != b && i != c {
+                    links[*x].push(*y);
+                    links[*y].push(*x);
+                }
+            }
+            let group = |start: usize, seen: &mut Vec<bool>| {
+                let mut stack = vec![start];
+                seen[start] = true;
+                let mut size = 0;
+                while let Some(at) = stack.pop() {
+                    size += 1;
+                    for next in &links[at] {
+                        if !seen[*next] {
+                            seen[*next] = true;
+                            stack.push(*next);
+                        }
+                    }
+                }
+                size
+            };
+            let mut seen = vec![false; n];
+            let first = group(0, &mut seen);
+            let other = seen.iter().position(|s| !s)?;
+            let second = group(other, &mut seen);
+            (first + second == n).then_some(first * second)
+        })
+        .unique()
+        .exactly_one()
+        .ok()?;
+    Some(sizes.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::{generate, solution};
+
+    /// Both parts of `day` on a generated input give the reference answer.
+    fn agrees(day: u8, seed: u64) -> Result<(), TestCaseError> {
+        let entry = solution::day(day).unwrap();
+        let input = generate::input(day, seed).unwrap();
+        let parsed = entry.parse(&input).unwrap();
+        for part in 1..=entry.parts {
+            if let Some(expected) = super::solve(day, part, &input) {
+                let answer = entry.solve(&parsed, part).unwrap().to_string();
+                prop_assert_eq!(answer, expected, "part {}, input:\n{}", part, input);
+            }
+        }
+        Ok(())
+    }
+
+    // Part 1 counts crossings inside a fixed test area, only hail at the
+    // scale of the real inputs gives the comparison something to count.
+    #[test]
+    fn day24_crossings() {
+        let crossing = (0..4)
+            .filter(|seed| {
+                super::solve(24, 1, &generate::input(24, *seed).unwrap()).unwrap() != "0"
+            })
+            .count();
+        assert!(crossing >= 3);
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(8))]
+
+        #[test]
+        fn day1(seed: u64) { agrees(1, seed)?; }
+        #[test]
+        fn day2(seed: u64) { agrees(2, seed)?; }
+        #[test]
+        fn day3(seed: u64) { agrees(3, seed)?; }
+        #[test]
+        fn day4(seed: u64) { agrees(4, seed)?; }
+        #[test]
+        fn day5(seed: u64) { agrees(5, seed)?; }
+        #[test]
+        fn day6(seed: u64) { agrees(6, seed)?; }
+        #[test]
+        fn day7(seed: u64) { agrees(7, seed)?; }
+        #[test]
+        fn day8(seed: u64) { agrees(8, seed)?; }
+        #[test]
+        fn day9(seed: u64) { agrees(9, seed)?; }
+        #[test]
+        fn day10(seed: u64) { agrees(10, seed)?; }
+        #[test]
+        fn day11(seed: u64) { agrees(11, seed)?; }
+        #[test]
+        fn day12(seed: u64) { agrees(12, seed)?; }
+        #[test]
+        fn day13(seed: u64) { agrees(13, seed)?; }
+        #[test]
+        fn day14(seed: u64) { agrees(14, seed)?; }
+        #[test]
+        fn day15(seed: u64) { agrees(15, seed)?; }
+        #[test]
+        fn day16(seed: u64) { agrees(16, seed)?; }
+        #[test]
+        fn day17(seed: u64) { agrees(17, seed)?; }
+        #[test]
+        fn day18(seed: u64) { agrees(18, seed)?; }
+        #[test]
+        fn day19(seed: u64) { agrees(19, seed)?; }
+        #[test]
+        fn day20(seed: u64) { agrees(20, seed)?; }
+        #[test]
+        fn day21(seed: u64) { agrees(21, seed)?; }
+        #[test]
+        fn day22(seed: u64) { agrees(22, seed)?; }
+        #[test]
+        fn day23(seed: u64) { agrees(23, seed)?; }
+        #[test]
+        fn day24(seed: u64) { agrees(24, seed)?; }
+        #[test]
+        fn day25(seed: u64) { agrees(25, seed)?; }
+    }
+}
@@ -0,0 +1,170 @@
+use std::{
+    any::Any,
+    panic::{catch_unwind, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use serde::{Serialize, Serializer};
+
+use crate::{
+    solution::{Entry, Parsed},
+    Answer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Ok,
+    ParseError,
+    Panic,
+}
+
+/// Outcome of running one part of a day.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartReport {
+    pub day: u8,
+    pub title: &'static str,
+    pub part: u8,
+    pub status: Status,
+    #[serde(serialize_with = "optional_display")]
+    pub answer: Option<Answer>,
+    /// [`Answer::kind`] of the answer.
+    pub answer_type: Option<&'static str>,
+    #[serde(rename = "parse_time_ns", serialize_with = "nanos")]
+    pub parse_time: Duration,
+    /// `None` if the input didn't parse.
+    #[serde(rename = "solve_time_ns", serialize_with = "optional_nanos")]
+    pub solve_time: Option<Duration>,
+    /// Parse error or panic message.
+    pub error: Option<String>,
+}
+
+impl PartReport {
+    fn new(entry: &Entry, part: u8, parse_time: Duration) -> Self {
+        Self {
+            day: entry.day,
+            title: entry.title,
+            part,
+            status: Status::Ok,
+            answer: None,
+            answer_type: None,
+            parse_time,
+            solve_time: None,
+            error: None,
+        }
+    }
+}
+
+/// Parses `input` and solves each of `parts` the day has, catching panics
+/// so one broken day doesn't stop the others.
+pub fn run(entry: &Entry, input: &str, parts: &[u8]) -> Vec<PartReport> {
+    let parts = parts
+        .iter()
+        .copied()
+        .filter(|p| (1..=entry.parts).contains(p));
+
+    let start = Instant::now();
+    let parsed = catch_unwind(AssertUnwindSafe(|| entry.parse(input)));
+    let parse_time = start.elapsed();
+    let (status, error) = match parsed {
+        Ok(Ok(parsed)) => return solve(entry, &parsed, parts, parse_time),
+        Ok(Err(e)) => (Status::ParseError, e.to_string()),
+        Err(payload) => (Status::Panic, panic_message(payload)),
+    };
+    parts
+        .map(|part| PartReport {
+            status,
+            error: Some(error.clone()),
+            ..PartReport::new(entry, part, parse_time)
+        })
+        .collect()
+}
+
+fn solve(
+    entry: &Entry,
+    parsed: &Parsed,
+    parts: impl Iterator<Item = u8>,
+    parse_time: Duration,
+) -> Vec<PartReport> {
+    parts
+        .map(|part| {
+            let start = Instant::now();
+            let answer = catch_unwind(AssertUnwindSafe(|| entry.solve(parsed, part).unwrap()));
+            let solve_time = Some(start.elapsed());
+            let report = PartReport::new(entry, part, parse_time);
+            match answer {
+                Ok(answer) => PartReport {
+                    answer_type: Some(answer.kind()),
+                    answer: Some(answer),
+                    solve_time,
+                    ..report
+                },
+                Err(payload) => PartReport {
+                    status: Status::Panic,
+                    solve_time,
+                    error: Some(panic_message(payload)),
+                    ..report
+                },
+            }
+        })
+        .collect()
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(s) => *s,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(s) => s.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+fn optional_display<S: Serializer>(a: &Option<Answer>, s: S) -> Result<S::Ok, S::Error> {
+    match a {
+        Some(a) => s.collect_str(a),
+        None => s.serialize_none(),
+    }
+}
+
+fn nanos<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u64(d.as_nanos() as u64)
+}
+
+fn optional_nanos<S: Serializer>(d: &Option<Duration>, s: S) -> Result<S::Ok, S::Error> {
+    match d {
+        Some(d) => nanos(d, s),
+        None => s.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Status;
+    use crate::{solution, Answer};
+
+    #[test]
+    fn parse_error() {
+        let reports = super::run(solution::day(9).unwrap(), "1 2 x\n", &[1, 2]);
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|r| r.status == Status::ParseError));
+        let json = serde_json::to_value(&reports[0]).unwrap();
+        assert_eq!(json["status"], "parse_error");
+        assert_eq!(json["solve_time_ns"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn ok() {
+        let reports = super::run(solution::day(9).unwrap(), "0 3 6 9 12 15\n", &[1, 2]);
+        let answers = reports.iter().map(|r| r.answer.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            answers,
+            [Some(Answer::Signed(18)), Some(Answer::Signed(-3))]
+        );
+        let json = serde_json::to_value(&reports[0]).unwrap();
+        assert_eq!(
+            (&json["answer"], &json["answer_type"]),
+            (&"18".into(), &"signed".into())
+        );
+    }
+}
@@ -0,0 +1,299 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// Cheapest path to a goal with the states along it, start and goal
+/// included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<S> {
+    pub cost: usize,
+    pub states: Vec<S>,
+}
+
+/// Dijkstra's shortest path from any of `starts` to the first state
+/// `is_goal` accepts, `successors` returns the next states with the cost of
+/// moving to them.
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    astar(starts, successors, |_| 0, is_goal)
+}
+
+/// A* search, same as [`dijkstra`] guided by `heuristic`, which must never
+/// overestimate the remaining cost to a goal and never drop by more than
+/// the cost of a move.
+pub fn astar<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    // States are numbered in discovery order so the heap doesn't need them
+    // to be Ord.
+    let mut states = Vec::new();
+    let mut index = HashMap::new();
+    // Best known cost, parent and whether the state is settled.
+    let mut nodes: Vec<(usize, Option<usize>, bool)> = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if let Entry::Vacant(v) = index.entry(start.clone()) {
+            v.insert(states.len());
+            heap.push(Reverse((heuristic(&start), 0, states.len())));
+            nodes.push((0, None, false));
+            states.push(start);
+        }
+    }
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        if std::mem::replace(&mut nodes[i].2, true) {
+            continue;
+        }
+        if is_goal(&states[i]) {
+            let mut path = vec![i];
+            while let Some(parent) = nodes[*path.last().unwrap()].1 {
+                path.push(parent);
+            }
+            let states = path.into_iter().rev().map(|i| states[i].clone()).collect();
+            return Some(Path { cost, states });
+        }
+        for (next, step) in successors(&states[i]) {
+            let next_cost = cost + step;
+            let j = match index.entry(next) {
+                Entry::Occupied(o) => {
+                    let j = *o.get();
+                    let (best, parent, settled) = &mut nodes[j];
+                    if *settled || *best <= next_cost {
+                        continue;
+                    }
+                    *best = next_cost;
+                    *parent = Some(i);
+                    j
+                }
+                Entry::Vacant(v) => {
+                    let j = states.len();
+                    states.push(v.key().clone());
+                    v.insert(j);
+                    nodes.push((next_cost, Some(i), false));
+                    j
+                }
+            };
+            heap.push(Reverse((next_cost + heuristic(&states[j]), next_cost, j)));
+        }
+    }
+    None
+}
+
+/// States reached by [`bfs`] with their distance to the closest start and
+/// the state they were reached from.
+#[derive(Debug, Clone)]
+pub struct Reached<S> {
+    pub distances: HashMap<S, usize>,
+    pub parents: HashMap<S, S>,
+}
+
+impl<S: Hash + Eq + Clone> Reached<S> {
+    /// Path from a start to `to`, both included.
+    pub fn path(&self, to: &S) -> Option<Vec<S>> {
+        self.distances.get(to)?;
+        let mut path = vec![to.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Same as [`Reached`] for states numbered `0..len`, like grid indices.
+#[derive(Debug, Clone)]
+pub struct GridReached {
+    pub distances: Vec<Option<usize>>,
+    pub parents: Vec<Option<usize>>,
+}
+
+impl GridReached {
+    pub fn path(&self, to: usize) -> Option<Vec<usize>> {
+        self.distances[to]?;
+        let mut path = vec![to];
+        while let Some(parent) = self.parents[*path.last().unwrap()] {
+            path.push(parent);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Reached states with their distance.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.distances
+            .iter()
+            .enumerate()
+            .filter_map(|(i, d)| Some((i, (*d)?)))
+    }
+}
+
+/// Breadth first search from `starts`, stops expanding states `max_depth`
+/// away if given.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    max_depth: Option<usize>,
+) -> Reached<S>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut reached = Reached {
+        distances: HashMap::new(),
+        parents: HashMap::new(),
+    };
+    walk(starts, neighbours, max_depth, |s, distance, parent| {
+        if reached.distances.contains_key(s) {
+            return false;
+        }
+        reached.distances.insert(s.clone(), distance);
+        if let Some(p) = parent {
+            reached.parents.insert(s.clone(), p.clone());
+        }
+        true
+    });
+    reached
+}
+
+/// [`bfs`] over states numbered `0..len`, keeps the results in `Vec`s.
+pub fn grid_bfs<I>(
+    len: usize,
+    starts: impl IntoIterator<Item = usize>,
+    neighbours: impl FnMut(&usize) -> I,
+    max_depth: Option<usize>,
+) -> GridReached
+where
+    I: IntoIterator<Item = usize>,
+{
+    let mut reached = GridReached {
+        distances: vec![None; len],
+        parents: vec![None; len],
+    };
+    walk(starts, neighbours, max_depth, |s, distance, parent| {
+        if reached.distances[*s].is_some() {
+            return false;
+        }
+        reached.distances[*s] = Some(distance);
+        reached.parents[*s] = parent.copied();
+        true
+    });
+    reached
+}
+
+/// `visit` records a state and returns whether it's new.
+fn walk<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    max_depth: Option<usize>,
+    mut visit: impl FnMut(&S, usize, Option<&S>) -> bool,
+) where
+    I: IntoIterator<Item = S>,
+{
+    let mut queue = VecDeque::new();
+    for s in starts {
+        if visit(&s, 0, None) {
+            queue.push_back((s, 0));
+        }
+    }
+    while let Some((s, distance)) = queue.pop_front() {
+        if max_depth.is_some_and(|m| distance >= m) {
+            continue;
+        }
+        for n in neighbours(&s) {
+            if visit(&n, distance + 1, Some(&s)) {
+                queue.push_back((n, distance + 1));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, bfs, dijkstra, grid_bfs, Path};
+
+    /// a -1-> b -1-> d, a -3-> c -1-> d, b -5-> c
+    fn edges(s: &char) -> Vec<(char, usize)> {
+        match s {
+            'a' => vec![('b', 1), ('c', 3)],
+            'b' => vec![('d', 1), ('c', 5)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn shortest() {
+        let path = dijkstra(['a'], edges, |s| *s == 'c');
+        assert_eq!(
+            path,
+            Some(Path {
+                cost: 3,
+                states: vec!['a', 'c'],
+            })
+        );
+        let path = dijkstra(['a'], edges, |s| *s == 'd').unwrap();
+        assert_eq!((path.cost, path.states), (2, vec!['a', 'b', 'd']));
+        assert_eq!(dijkstra(['d'], edges, |s| *s == 'a'), None);
+    }
+
+    #[test]
+    fn breadth_first() {
+        let neighbours = |s: &char| edges(s).into_iter().map(|(n, _)| n);
+        let reached = bfs(['a'], neighbours, None);
+        assert_eq!(reached.distances[&'d'], 2);
+        assert_eq!(reached.path(&'d'), Some(vec!['a', 'b', 'd']));
+        assert_eq!(reached.path(&'x'), None);
+        let reached = bfs(['a'], neighbours, Some(1));
+        assert_eq!(reached.distances.len(), 3);
+
+        // 0 - 1 - 2 - 3 - 4
+        let line = |i: &usize| {
+            [i.checked_sub(1), Some(i + 1).filter(|i| *i < 5)]
+                .into_iter()
+                .flatten()
+        };
+        let reached = grid_bfs(5, [2], line, None);
+        assert_eq!(
+            reached.distances,
+            [Some(2), Some(1), Some(0), Some(1), Some(2)]
+        );
+        assert_eq!(reached.path(4), Some(vec![2, 3, 4]));
+        let reached = grid_bfs(5, [0], line, Some(2));
+        assert_eq!(reached.iter().collect::<Vec<_>>(), [(0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn grid() {
+        // Walking a 10x10 grid, the manhattan distance keeps A* exact.
+        let successors = |&(x, y): &(i32, i32)| {
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .into_iter()
+                .map(move |(dx, dy)| (x + dx, y + dy))
+                .filter(|(x, y)| (0..10).contains(x) && (0..10).contains(y) && (*x, *y) != (5, 5))
+                .map(|s| (s, 1))
+        };
+        let goal = (9, 9);
+        let heuristic = |&(x, y): &(i32, i32)| (goal.0 - x + goal.1 - y) as usize;
+        let a = astar([(0, 0)], successors, heuristic, |s| *s == goal).unwrap();
+        let d = dijkstra([(0, 0)], successors, |s| *s == goal).unwrap();
+        assert_eq!((a.cost, d.cost), (18, 18));
+        assert_eq!(a.states.len(), 19);
+    }
+}
@@ -0,0 +1,157 @@
+use std::any::Any;
+
+use crate::{
+    image::{Image, Recorder},
+    *,
+};
+
+/// A day's puzzle, split into a parse step shared by both parts.
+pub trait Solution {
+    type Input: 'static;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    const DAY: u8;
+    const TITLE: &'static str;
+    /// Day 25 only has one puzzle.
+    const HAS_PART_2: bool = true;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part_1(input: &Self::Input) -> Self::Part1;
+    fn part_2(input: &Self::Input) -> Self::Part2;
+
+    /// Picture of the input or the solution, for the days with a grid.
+    fn render(_input: &Self::Input) -> Option<Image> {
+        None
+    }
+
+    /// Same as [`Solution::render`] with ANSI colours for the terminal.
+    fn terminal(_input: &Self::Input) -> Option<String> {
+        None
+    }
+
+    /// Records the steps of a simulation, for the days with one.
+    fn animate(_input: &Self::Input, _recorder: &mut Recorder) {}
+}
+
+/// Parsed input of a day in the registry.
+pub struct Parsed {
+    day: u8,
+    input: Box<dyn Any>,
+}
+
+/// Type erased [`Solution`] so days can be iterated over generically.
+pub struct Entry {
+    pub day: u8,
+    pub title: &'static str,
+    pub parts: u8,
+    parse: fn(&str) -> Result<Box<dyn Any>, ParseError>,
+    part_1: fn(&dyn Any) -> Answer,
+    part_2: fn(&dyn Any) -> Answer,
+    render: fn(&dyn Any) -> Option<Image>,
+    terminal: fn(&dyn Any) -> Option<String>,
+    animate: fn(&dyn Any, &mut Recorder),
+}
+
+impl Entry {
+    const fn new<S: Solution>() -> Self {
+        fn parse<S: Solution>(input: &str) -> Result<Box<dyn Any>, ParseError> {
+            Ok(Box::new(S::parse(input)?))
+        }
+        fn part_1<S: Solution>(input: &dyn Any) -> Answer {
+            S::part_1(input.downcast_ref().unwrap()).into()
+        }
+        fn part_2<S: Solution>(input: &dyn Any) -> Answer {
+            S::part_2(input.downcast_ref().unwrap()).into()
+        }
+        fn render<S: Solution>(input: &dyn Any) -> Option<Image> {
+            S::render(input.downcast_ref().unwrap())
+        }
+        fn terminal<S: Solution>(input: &dyn Any) -> Option<String> {
+            S::terminal(input.downcast_ref().unwrap())
+        }
+        fn animate<S: Solution>(input: &dyn Any, recorder: &mut Recorder) {
+            S::animate(input.downcast_ref().unwrap(), recorder)
+        }
+        Self {
+            day: S::DAY,
+            title: S::TITLE,
+            parts: if S::HAS_PART_2 { 2 } else { 1 },
+            parse: parse::<S>,
+            part_1: part_1::<S>,
+            part_2: part_2::<S>,
+            render: render::<S>,
+            terminal: terminal::<S>,
+            animate: animate::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        Ok(Parsed {
+            day: self.day,
+            input: (self.parse)(input)?,
+        })
+    }
+
+    /// Solves `part` on input parsed by this entry, returns `None` if the
+    /// day doesn't have that part.
+    pub fn solve(&self, input: &Parsed, part: u8) -> Option<Answer> {
+        assert_eq!(input.day, self.day, "input parsed for another day");
+        let input = input.input.as_ref();
+        match part {
+            1 => Some((self.part_1)(input)),
+            2 if self.parts == 2 => Some((self.part_2)(input)),
+            _ => None,
+        }
+    }
+
+    /// `None` if the day has nothing to draw.
+    pub fn render(&self, input: &Parsed) -> Option<Image> {
+        assert_eq!(input.day, self.day, "input parsed for another day");
+        (self.render)(input.input.as_ref())
+    }
+
+    /// `None` if the day has nothing to draw.
+    pub fn terminal(&self, input: &Parsed) -> Option<String> {
+        assert_eq!(input.day, self.day, "input parsed for another day");
+        (self.terminal)(input.input.as_ref())
+    }
+
+    /// Records nothing if the day has no simulation.
+    pub fn animate(&self, input: &Parsed, recorder: &mut Recorder) {
+        assert_eq!(input.day, self.day, "input parsed for another day");
+        (self.animate)(input.input.as_ref(), recorder)
+    }
+}
+
+pub static DAYS: [Entry; 25] = [
+    Entry::new::<day1::Day1>(),
+    Entry::new::<day2::Day2>(),
+    Entry::new::<day3::Day3>(),
+    Entry::new::<day4::Day4>(),
+    Entry::new::<day5::Day5>(),
+    Entry::new::<day6::Day6>(),
+    Entry::new::<day7::Day7>(),
+    Entry::new::<day8::Day8>(),
+    Entry::new::<day9::Day9>(),
+    Entry::new::<day10::Day10>(),
+    Entry::new::<day11::Day11>(),
+    Entry::new::<day12::Day12>(),
+    Entry::new::<day13::Day13>(),
+    Entry::new::<day14::Day14>(),
+    Entry::new::<day15::Day15>(),
+    Entry::new::<day16::Day16>(),
+    Entry::new::<day17::Day17>(),
+    Entry::new::<day18::Day18>(),
+    Entry::new::<day19::Day19>(),
+    Entry::new::<day20::Day20>(),
+    Entry::new::<day21::Day21>(),
+    Entry::new::<day22::Day22>(),
+    Entry::new::<day23::Day23>(),
+    Entry::new::<day24::Day24>(),
+    Entry::new::<day25::Day25>(),
+];
+
+pub fn day(day: u8) -> Option<&'static Entry> {
+    DAYS.iter().find(|e| e.day == day)
+}
@@ -0,0 +1,165 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use crate::{Direction, Map, Vector};
+
+/// ANSI terminal colours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Default,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    fn foreground(self) -> u8 {
+        match self {
+            Self::Default => 39,
+            Self::Black => 30,
+            Self::Red => 31,
+            Self::Green => 32,
+            Self::Yellow => 33,
+            Self::Blue => 34,
+            Self::Magenta => 35,
+            Self::Cyan => 36,
+            Self::White => 37,
+            Self::Grey => 90,
+        }
+    }
+
+    fn background(self) -> u8 {
+        self.foreground() + 10
+    }
+}
+
+/// A map drawn with ANSI colours, see [`Map::terminal`].
+pub struct Terminal<'a, T, F> {
+    map: &'a Map<T>,
+    glyph: F,
+    path: HashMap<usize, char>,
+    highlighted: HashSet<usize>,
+    cursor: Option<usize>,
+}
+
+impl<T> Map<T> {
+    /// Draws each node as the character and colour `glyph` gives it.
+    pub fn terminal<F: Fn(&T) -> (char, Colour)>(&self, glyph: F) -> Terminal<'_, T, F> {
+        Terminal {
+            map: self,
+            glyph,
+            path: HashMap::new(),
+            highlighted: HashSet::new(),
+            cursor: None,
+        }
+    }
+}
+
+impl<'a, T, F: Fn(&T) -> (char, Colour)> Terminal<'a, T, F> {
+    /// Draws the steps of `path` as red arrows.
+    pub fn path(mut self, path: impl IntoIterator<Item = Vector>) -> Self {
+        let converter = self.map.converter();
+        let mut prev = None;
+        for v in path {
+            let arrow = prev
+                .and_then(|p| Direction::from_vector(v - p))
+                .map_or('*', arrow);
+            if let Some(i) = converter.to_idx(v) {
+                self.path.insert(i, arrow);
+            }
+            prev = Some(v);
+        }
+        self
+    }
+
+    /// Puts the nodes at `indices` on a blue background.
+    pub fn highlight(mut self, indices: impl IntoIterator<Item = usize>) -> Self {
+        self.highlighted.extend(indices);
+        self
+    }
+
+    /// Draws the node at `v` in reverse video.
+    pub fn cursor(mut self, v: Vector) -> Self {
+        self.cursor = self.map.converter().to_idx(v);
+        self
+    }
+}
+
+fn arrow(d: Direction) -> char {
+    match d {
+        Direction::Up => '^',
+        Direction::Down => 'v',
+        Direction::Left => '<',
+        Direction::Right => '>',
+        _ => '*',
+    }
+}
+
+impl<'a, T, F: Fn(&T) -> (char, Colour)> Display for Terminal<'a, T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, node) in self.map.nodes().iter().enumerate() {
+            let (mut c, mut colour) = (self.glyph)(node);
+            if let Some(arrow) = self.path.get(&i) {
+                (c, colour) = (*arrow, Colour::Red);
+            }
+            write!(f, "\x1b[{}", colour.foreground())?;
+            if self.highlighted.contains(&i) {
+                write!(f, ";{}", Colour::Blue.background())?;
+            }
+            if self.cursor == Some(i) {
+                write!(f, ";7")?;
+            }
+            write!(f, "m{c}\x1b[0m")?;
+            if (i + 1) % self.map.cols() == 0 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Colour;
+    use crate::{Map, Vector};
+
+    #[test]
+    fn overlays() {
+        let map = Map::new(vec!['.'; 4], 2);
+        let plain = map.terminal(|c| (*c, Colour::Default)).to_string();
+        assert_eq!(
+            plain,
+            "\x1b[39m.\x1b[0m\x1b[39m.\x1b[0m\n\x1b[39m.\x1b[0m\x1b[39m.\x1b[0m\n"
+        );
+
+        let drawn = map
+            .terminal(|c| (*c, Colour::Grey))
+            .path([Vector::new(0, 0), Vector::new(1, 0), Vector::new(1, 1)])
+            .highlight([2])
+            .cursor(Vector::new(1, 1))
+            .to_string();
+        let cells = drawn
+            .lines()
+            .flat_map(|l| l.split("\x1b[0m"))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cells,
+            [
+                "\x1b[31m*",
+                "\x1b[31m>",
+                "",
+                "\x1b[90;44m.",
+                "\x1b[31;7mv",
+                ""
+            ]
+        );
+    }
+}